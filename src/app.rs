@@ -1,12 +1,14 @@
 use rinse::config::{load_ratchets, Config, ConfigError, InterfaceConfig};
-use rinse::{Interface, Node, ServiceId, TcpTransport};
+use rinse::{Node, ServiceId, TcpTransport};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
+use tokio::net::TcpListener;
 use tokio::sync::Mutex;
 
 use crate::identity::{Identity, IdentityError};
+use crate::messages;
 
 const RATCHET_INTERVAL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
 
@@ -26,7 +28,11 @@ pub struct NomadApp {
     node: Arc<Mutex<Node<TcpTransport>>>,
     service_id: Option<ServiceId>,
     dest_hash: [u8; 16],
+    message_service_id: Option<ServiceId>,
+    message_address: [u8; 16],
     interface_status: HashMap<String, bool>,
+    /// Bound TCP server sockets, accepted on once the node is running.
+    listeners: HashMap<String, TcpListener>,
     announced_on_startup: bool,
 }
 
@@ -45,6 +51,15 @@ impl NomadApp {
 
         log::info!("Our address: {}", hex::encode(dest_hash));
 
+        let message_service_id = node.add_service(
+            messages::APP_NAME,
+            &[messages::DELIVERY_ASPECT],
+            identity.inner(),
+        );
+        let message_address = node.service_address(message_service_id).unwrap();
+
+        log::info!("Our message address: {}", hex::encode(message_address));
+
         let existing_ratchets = load_ratchets(&dest_hash).ok();
         let ratchet_count = existing_ratchets.as_ref().map(|r| r.len()).unwrap_or(0);
         node.enable_ratchets(service_id, RATCHET_INTERVAL, existing_ratchets);
@@ -56,42 +71,39 @@ impl NomadApp {
         }
 
         let mut interface_status = HashMap::new();
+        let mut listeners = HashMap::new();
 
         for (name, iface_config) in &enabled_interfaces {
             match iface_config {
-                InterfaceConfig::TCPClientInterface {
-                    target_host,
-                    target_port,
+                // Clients are dialed by their supervisors once the node runs.
+                InterfaceConfig::TCPClientInterface { .. } => {
+                    interface_status.insert(name.to_string(), false);
+                }
+                InterfaceConfig::TCPServerInterface {
+                    listen_ip,
+                    listen_port,
                     ..
                 } => {
-                    let addr = format!("{}:{}", target_host, target_port);
-                    log::info!("Connecting to {} ({})", name, addr);
-                    match TcpTransport::connect(&addr).await {
-                        Ok(transport) => {
-                            node.add_interface(Interface::new(transport));
+                    let addr = crate::config_file::interface_address(listen_ip, *listen_port);
+                    log::info!("Listening on {} ({})", name, addr);
+                    match TcpListener::bind(&addr).await {
+                        Ok(listener) => {
+                            listeners.insert(name.to_string(), listener);
                             interface_status.insert(name.to_string(), true);
                         }
                         Err(e) => {
-                            log::warn!("Failed to connect to {}: {}", addr, e);
+                            log::warn!("Failed to listen on {}: {}", addr, e);
                             interface_status.insert(name.to_string(), false);
                         }
                     }
                 }
-                InterfaceConfig::TCPServerInterface {
-                    listen_ip,
-                    listen_port,
-                    ..
-                } => {
-                    let addr = format!("{}:{}", listen_ip, listen_port);
-                    log::info!("TCP server {} ({}) - not yet supported", name, addr);
-                    interface_status.insert(name.to_string(), false);
-                }
             }
         }
 
         let announced_on_startup = !enabled_interfaces.is_empty();
         if announced_on_startup {
             node.announce(service_id);
+            node.announce(message_service_id);
             log::info!("Announced on network");
         }
 
@@ -101,7 +113,10 @@ impl NomadApp {
             node: Arc::new(Mutex::new(node)),
             service_id: Some(service_id),
             dest_hash,
+            message_service_id: Some(message_service_id),
+            message_address,
             interface_status,
+            listeners,
             announced_on_startup,
         })
     }
@@ -118,6 +133,16 @@ impl NomadApp {
         self.service_id.take().expect("service_id already taken")
     }
 
+    pub fn message_address(&self) -> [u8; 16] {
+        self.message_address
+    }
+
+    pub fn take_message_service_id(&mut self) -> ServiceId {
+        self.message_service_id
+            .take()
+            .expect("message_service_id already taken")
+    }
+
     pub fn relay_enabled(&self) -> bool {
         self.config.network.relay
     }
@@ -126,6 +151,10 @@ impl NomadApp {
         &self.interface_status
     }
 
+    pub fn take_listeners(&mut self) -> HashMap<String, TcpListener> {
+        std::mem::take(&mut self.listeners)
+    }
+
     pub fn announced_on_startup(&self) -> bool {
         self.announced_on_startup
     }
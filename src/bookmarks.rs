@@ -0,0 +1,131 @@
+use crate::network::split_page_url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const BOOKMARKS_PATH: &str = ".rinse/bookmarks.toml";
+
+/// A saved page. `url` is the full `hash:/path` so reopening skips the
+/// node's index page, and `form_data` is resent with the request when set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub url: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub form_data: HashMap<String, String>,
+}
+
+impl Bookmark {
+    pub fn node_hash(&self) -> Option<[u8; 16]> {
+        split_page_url(&self.url).map(|(hash, _)| hash)
+    }
+
+    pub fn path(&self) -> &str {
+        split_page_url(&self.url)
+            .map(|(_, path)| path)
+            .unwrap_or("")
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    bookmarks: Vec<Bookmark>,
+}
+
+/// Bookmarks keyed by URL; saving a URL again replaces its title and form
+/// data.
+pub struct BookmarkStore {
+    path: PathBuf,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let bookmarks = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| toml::from_str::<BookmarksFile>(&c).ok())
+            .map(|f| f.bookmarks)
+            .unwrap_or_default();
+        Self { path, bookmarks }
+    }
+
+    pub fn all(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn save(&mut self, bookmark: Bookmark) {
+        match self.bookmarks.iter_mut().find(|b| b.url == bookmark.url) {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+        self.persist();
+    }
+
+    pub fn remove(&mut self, url: &str) -> Option<Bookmark> {
+        let pos = self.bookmarks.iter().position(|b| b.url == url)?;
+        let removed = self.bookmarks.remove(pos);
+        self.persist();
+        Some(removed)
+    }
+
+    /// Drops every bookmark on a node, used when the node itself is removed.
+    pub fn remove_node(&mut self, hash: &[u8; 16]) {
+        let before = self.bookmarks.len();
+        self.bookmarks
+            .retain(|b| b.node_hash().as_ref() != Some(hash));
+        if self.bookmarks.len() != before {
+            self.persist();
+        }
+    }
+
+    fn persist(&self) {
+        let file = BookmarksFile {
+            bookmarks: self.bookmarks.clone(),
+        };
+
+        if let Ok(contents) = toml::to_string_pretty(&file) {
+            let _ = fs::write(&self.path, contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_replace_and_remove_node() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("bookmarks.toml");
+        let node = hex::encode([0x11; 16]);
+        let other = hex::encode([0x22; 16]);
+
+        let mut store = BookmarkStore::new(&path);
+        store.save(Bookmark {
+            url: format!("{}:/page/board.mu", node),
+            title: "Board".into(),
+            form_data: HashMap::new(),
+        });
+        store.save(Bookmark {
+            url: format!("{}:/page/board.mu", node),
+            title: "Main Board".into(),
+            form_data: HashMap::from([("field_topic".into(), "mesh".into())]),
+        });
+        store.save(Bookmark {
+            url: format!("{}:/page/index.mu", other),
+            title: "Other".into(),
+            form_data: HashMap::new(),
+        });
+
+        let reloaded = BookmarkStore::new(&path);
+        assert_eq!(reloaded.all().len(), 2);
+        assert_eq!(reloaded.all()[0].title, "Main Board");
+        assert_eq!(reloaded.all()[0].path(), "/page/board.mu");
+        assert_eq!(reloaded.all()[0].form_data["field_topic"], "mesh");
+
+        store.remove_node(&[0x11; 16]);
+        assert_eq!(BookmarkStore::new(&path).all().len(), 1);
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    url: String,
+    fetched_at: i64,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+pub struct CachedResponse {
+    pub data: Vec<u8>,
+    pub fetched_at: i64,
+}
+
+/// Raw page and partial responses on disk, one file per `hash:path` and
+/// form data combination so a cached page can be shown before the node
+/// answers.
+pub struct PageCache {
+    dir: PathBuf,
+}
+
+impl PageCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        if let Err(e) = fs::create_dir_all(&dir) {
+            log::warn!("Failed to create cache dir {}: {}", dir.display(), e);
+        }
+        Self { dir }
+    }
+
+    pub async fn get(
+        &self,
+        url: &str,
+        form_data: &HashMap<String, String>,
+    ) -> Option<CachedResponse> {
+        let bytes = tokio::fs::read(self.entry_path(url, form_data))
+            .await
+            .ok()?;
+        let file: CacheFile = rmp_serde::from_slice(&bytes).ok()?;
+        // A hash collision is astronomically unlikely, but a mismatch is cheap to catch.
+        if file.url != url {
+            return None;
+        }
+        Some(CachedResponse {
+            data: file.data,
+            fetched_at: file.fetched_at,
+        })
+    }
+
+    pub async fn put(&self, url: &str, form_data: &HashMap<String, String>, data: &[u8]) {
+        let file = CacheFile {
+            url: url.to_string(),
+            fetched_at: chrono::Utc::now().timestamp(),
+            data: data.to_vec(),
+        };
+        let path = self.entry_path(url, form_data);
+        match rmp_serde::to_vec(&file) {
+            Ok(bytes) => {
+                if let Err(e) = tokio::fs::write(&path, bytes).await {
+                    log::warn!("Failed to cache {}: {}", url, e);
+                }
+            }
+            Err(e) => log::warn!("Failed to encode cache entry for {}: {}", url, e),
+        }
+    }
+
+    /// Removes entries that haven't been refreshed within `max_age`.
+    pub fn prune(&self, max_age: Duration) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let expired = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.elapsed().ok())
+                .is_some_and(|age| age >= max_age);
+            if expired {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    fn entry_path(&self, url: &str, form_data: &HashMap<String, String>) -> PathBuf {
+        self.dir.join(cache_key(url, form_data))
+    }
+}
+
+fn cache_key(url: &str, form_data: &HashMap<String, String>) -> String {
+    let mut fields: Vec<_> = form_data.iter().collect();
+    fields.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    for (key, value) in fields {
+        hasher.update([0]);
+        hasher.update(key.as_bytes());
+        hasher.update([0]);
+        hasher.update(value.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keyed_by_url_and_form_data() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = PageCache::new(tmp.path().join("cache"));
+        let url = "0011:/page/index.mu";
+        let form = HashMap::from([("field_q".to_string(), "mesh".to_string())]);
+
+        assert!(cache.get(url, &HashMap::new()).await.is_none());
+        cache.put(url, &HashMap::new(), b"plain").await;
+        cache.put(url, &form, b"searched").await;
+
+        assert_eq!(
+            cache.get(url, &HashMap::new()).await.unwrap().data,
+            b"plain"
+        );
+        assert_eq!(cache.get(url, &form).await.unwrap().data, b"searched");
+        assert!(cache.get("0011:/page/other.mu", &form).await.is_none());
+
+        cache.prune(Duration::ZERO);
+        assert!(cache.get(url, &form).await.is_none());
+    }
+}
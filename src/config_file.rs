@@ -0,0 +1,210 @@
+use rinse::config::{Config, InterfaceConfig};
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use thiserror::Error;
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+/// Where rinse keeps its config, relative to the working directory.
+pub const CONFIG_PATH: &str = ".rinse/config.toml";
+
+#[derive(Error, Debug)]
+pub enum ConfigFileError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("config.toml is not valid TOML: {0}")]
+    Parse(#[from] toml_edit::TomlError),
+    #[error("couldn't encode interface: {0}")]
+    Encode(#[from] toml_edit::ser::Error),
+    #[error("rinse wouldn't load the edited config: {0}")]
+    Invalid(#[from] toml::de::Error),
+}
+
+/// Address keys for each interface type; an entry only keeps its own.
+const CLIENT_KEYS: [&str; 2] = ["target_host", "target_port"];
+const SERVER_KEYS: [&str; 2] = ["listen_ip", "listen_port"];
+
+/// Writes `config` as `[interfaces.<name>]`, moving the entry if it was
+/// called `original`. The keys come from rinse's own encoding of the
+/// interface; keys nomad doesn't edit, comments and the rest of the file are
+/// left as they were.
+pub fn save_interface(
+    path: &Path,
+    original: Option<&str>,
+    name: &str,
+    config: &InterfaceConfig,
+) -> Result<(), ConfigFileError> {
+    let fields = toml_edit::ser::to_document(config)?;
+    edit(path, |interfaces| {
+        let mut entry = original
+            .and_then(|original| interfaces.remove(original))
+            .and_then(|item| item.into_table().ok())
+            .unwrap_or_default();
+
+        for key in CLIENT_KEYS.iter().chain(&SERVER_KEYS) {
+            if !fields.contains_key(key) {
+                entry.remove(key);
+            }
+        }
+        for (key, item) in fields.iter() {
+            match (
+                entry.get_mut(key).and_then(Item::as_value_mut),
+                item.as_value(),
+            ) {
+                // Keep the comment and spacing around a value being replaced.
+                (Some(old), Some(new)) => {
+                    let decor = old.decor().clone();
+                    *old = new.clone();
+                    *old.decor_mut() = decor;
+                }
+                _ => {
+                    entry.insert(key, item.clone());
+                }
+            }
+        }
+        interfaces.insert(name, Item::Table(entry));
+    })
+}
+
+/// `host:port` for binding or dialing an interface, with IPv6 literals in
+/// brackets.
+pub fn interface_address(host: &str, port: u16) -> String {
+    match host.parse::<IpAddr>() {
+        Ok(ip) => SocketAddr::new(ip, port).to_string(),
+        Err(_) => format!("{}:{}", host, port),
+    }
+}
+
+pub fn delete_interface(path: &Path, name: &str) -> Result<(), ConfigFileError> {
+    edit(path, |interfaces| {
+        interfaces.remove(name);
+    })
+}
+
+/// Applies `f` to the `[interfaces]` table and writes the file back. rinse's
+/// `Config` can't be saved, and serializing it would drop comments and keys it
+/// doesn't model, so the file is edited in place instead and only written if
+/// it still loads as a `Config`.
+fn edit(path: &Path, f: impl FnOnce(&mut Table)) -> Result<(), ConfigFileError> {
+    let mut doc: DocumentMut = match fs::read_to_string(path) {
+        Ok(contents) => contents.parse()?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => DocumentMut::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let interfaces = doc.entry("interfaces").or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    if let Item::Value(Value::InlineTable(inline)) = interfaces {
+        *interfaces = Item::Table(std::mem::take(inline).into_table());
+    }
+    if let Some(interfaces) = interfaces.as_table_mut() {
+        f(interfaces);
+    }
+    let contents = doc.to_string();
+    toml::from_str::<Config>(&contents)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = r#"# Managed by hand.
+[network]
+relay = true
+
+# The box under the stairs.
+[interfaces."Home"]
+type = "TCPClientInterface"
+enabled = true # flip to pause
+target_host = "10.0.0.2"
+target_port = 4242
+kiss_framing = false
+
+[interfaces."Old"]
+type = "TCPServerInterface"
+enabled = false
+listen_ip = "0.0.0.0"
+listen_port = 4242
+"#;
+
+    fn server() -> InterfaceConfig {
+        InterfaceConfig::TCPServerInterface {
+            enabled: true,
+            listen_ip: "127.0.0.1".into(),
+            listen_port: 4965,
+        }
+    }
+
+    #[test]
+    fn test_edits_interfaces_and_keeps_other_settings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        fs::write(&path, ORIGINAL).unwrap();
+
+        save_interface(&path, Some("Home"), "Hub", &server()).unwrap();
+        delete_interface(&path, "Old").unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("# Managed by hand.\n[network]\nrelay = true\n"));
+        assert!(contents.contains("# The box under the stairs.\n[interfaces.Hub]\n"));
+        assert!(contents.contains("enabled = true # flip to pause\n"));
+        assert!(!contents.contains("Old"));
+
+        let doc: toml::Table = toml::from_str(&contents).unwrap();
+        let interfaces = doc["interfaces"].as_table().unwrap();
+        let hub = interfaces["Hub"].as_table().unwrap();
+        assert_eq!(hub["listen_port"].as_integer(), Some(4965));
+        assert_eq!(hub["kiss_framing"].as_bool(), Some(false));
+        assert!(!hub.contains_key("target_host"));
+    }
+
+    #[test]
+    fn test_refuses_a_config_rinse_would_not_load() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        let broken = "[network]\nrelay = \"yes\"\n";
+        fs::write(&path, broken).unwrap();
+
+        let result = save_interface(&path, None, "Hub", &server());
+        assert!(matches!(result, Err(ConfigFileError::Invalid(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), broken);
+    }
+
+    #[test]
+    fn test_interface_address_brackets_ipv6() {
+        assert_eq!(interface_address("::", 4965), "[::]:4965");
+        assert_eq!(interface_address("fe80::1", 4242), "[fe80::1]:4242");
+        assert_eq!(interface_address("10.0.0.1", 4242), "10.0.0.1:4242");
+        assert_eq!(
+            interface_address("rns.example.org", 4242),
+            "rns.example.org:4242"
+        );
+        assert!(interface_address("::1", 4242).parse::<SocketAddr>().is_ok());
+    }
+
+    #[test]
+    fn test_rinse_loads_saved_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        fs::write(&path, ORIGINAL).unwrap();
+        save_interface(&path, Some("Home"), "Hub", &server()).unwrap();
+        save_interface(&path, None, "New", &server()).unwrap();
+
+        // Config::load only reads from the working directory, so parse the
+        // file the same way it does rather than changing directory.
+        let config: Config = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(config.network.relay);
+        assert_eq!(config.interfaces.len(), 3);
+        assert!(matches!(
+            config.interfaces["Hub"],
+            InterfaceConfig::TCPServerInterface {
+                listen_port: 4965,
+                ..
+            }
+        ));
+    }
+}
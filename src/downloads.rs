@@ -0,0 +1,414 @@
+use crate::network::NodeInfo;
+use serde_bytes::ByteBuf;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+pub const DOWNLOADS_DIR: &str = ".rinse/downloads";
+
+/// Characters that are unsafe in a file name on at least one platform.
+const RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+const MAX_NAME_CHARS: usize = 200;
+
+/// Request field asking a file host for one chunk, as `offset:len`. Only
+/// nomad hosts know it; stock NomadNet nodes ignore it and send the whole
+/// file, which then arrives in memory all at once.
+pub const RANGE_KEY: &str = "nomad_range";
+/// Tag that marks a response as a single chunk rather than a whole file.
+const CHUNK_TAG: &str = "nomad_chunk";
+/// Bytes asked for per request while downloading.
+pub const CHUNK_SIZE: u64 = 256 * 1024;
+/// Largest chunk a host will send back, whatever was asked for.
+pub const MAX_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const PART_SUFFIX: &str = ".part";
+
+#[derive(Error, Debug)]
+pub enum TargetError {
+    #[error("file name is empty")]
+    Empty,
+    #[error("\"{0}\" would leave the download folder")]
+    Traversal(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadJob {
+    pub transfer_id: u64,
+    pub node: NodeInfo,
+    pub path: String,
+    pub filename: String,
+    pub dir: PathBuf,
+}
+
+impl DownloadJob {
+    /// Where the download is written while it's in flight. The name carries
+    /// a short hash of the node and path, so only a later download of the
+    /// same file into the same folder picks it up.
+    pub fn part_path(&self) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(self.node.hash);
+        hasher.update(self.path.as_bytes());
+        let source = hex::encode(&hasher.finalize()[..4]);
+        self.dir
+            .join(format!("{}.{}{}", self.filename, source, PART_SUFFIX))
+    }
+}
+
+/// One piece of a file, sent in reply to a [`RANGE_KEY`] request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChunk {
+    pub offset: u64,
+    pub total: u64,
+    pub data: Vec<u8>,
+}
+
+impl FileChunk {
+    pub fn encode(&self) -> Vec<u8> {
+        let chunk = (
+            CHUNK_TAG,
+            self.offset,
+            self.total,
+            ByteBuf::from(self.data.clone()),
+        );
+        rmp_serde::to_vec(&chunk).unwrap_or_default()
+    }
+
+    /// Returns `None` for anything that isn't a chunk, which is how a whole
+    /// file from a host without range support is told apart.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let (tag, offset, total, data) =
+            rmp_serde::from_slice::<(String, u64, u64, ByteBuf)>(data).ok()?;
+        (tag == CHUNK_TAG).then(|| Self {
+            offset,
+            total,
+            data: data.into_vec(),
+        })
+    }
+}
+
+/// The request fields asking for `len` bytes starting at `offset`.
+pub fn range_request(offset: u64, len: u64) -> HashMap<String, String> {
+    HashMap::from([(RANGE_KEY.to_string(), format!("{}:{}", offset, len))])
+}
+
+/// Reads a [`RANGE_KEY`] value back into `(offset, len)`.
+pub fn parse_range(value: &str) -> Option<(u64, u64)> {
+    let (offset, len) = value.split_once(':')?;
+    Some((offset.parse().ok()?, len.parse().ok()?))
+}
+
+struct ActiveDownload {
+    transfer_id: u64,
+    offset: u64,
+    total: Option<u64>,
+    /// A chunk request is on the wire, so resource progress may be ours.
+    fetching: bool,
+}
+
+/// Runs downloads one at a time. rinse reports resource progress per
+/// service rather than per transfer, so a single active transfer is what
+/// lets progress be attributed to the right download.
+#[derive(Default)]
+pub struct DownloadQueue {
+    queued: VecDeque<DownloadJob>,
+    active: Option<ActiveDownload>,
+}
+
+impl DownloadQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, job: DownloadJob) {
+        self.queued.push_back(job);
+    }
+
+    pub fn active(&self) -> Option<u64> {
+        self.active.as_ref().map(|a| a.transfer_id)
+    }
+
+    /// Hands out the next job if nothing is transferring.
+    pub fn start_next(&mut self) -> Option<DownloadJob> {
+        if self.active.is_some() {
+            return None;
+        }
+        let job = self.queued.pop_front()?;
+        self.active = Some(ActiveDownload {
+            transfer_id: job.transfer_id,
+            offset: 0,
+            total: None,
+            fetching: false,
+        });
+        Some(job)
+    }
+
+    /// Records how much of the active download is already on disk, so
+    /// progress of the chunk in flight can be reported against the file.
+    pub fn set_offset(&mut self, transfer_id: u64, offset: u64, total: Option<u64>) {
+        if let Some(active) = self.active.as_mut() {
+            if active.transfer_id == transfer_id {
+                active.offset = offset;
+                active.total = total.or(active.total);
+            }
+        }
+    }
+
+    pub fn set_fetching(&mut self, transfer_id: u64, fetching: bool) {
+        if let Some(active) = self.active.as_mut() {
+            if active.transfer_id == transfer_id {
+                active.fetching = fetching;
+            }
+        }
+    }
+
+    /// Maps progress of the resource in flight to `(transfer_id, received,
+    /// total)` for the whole file, or `None` if no chunk is being fetched.
+    pub fn progress(&self, received_bytes: usize, total_bytes: usize) -> Option<(u64, u64, u64)> {
+        let active = self.active.as_ref().filter(|a| a.fetching)?;
+        let received = active.offset + received_bytes as u64;
+        let total = active
+            .total
+            .unwrap_or(active.offset + total_bytes as u64)
+            .max(received);
+        Some((active.transfer_id, received, total))
+    }
+
+    pub fn finish(&mut self, transfer_id: u64) {
+        if self.active() == Some(transfer_id) {
+            self.active = None;
+        }
+    }
+
+    /// Drops a queued job, returning it. The active one keeps its slot until
+    /// its task has wound down and calls `finish`, so the next transfer can't
+    /// start while the old one is still on the link.
+    pub fn cancel(&mut self, transfer_id: u64) -> Option<DownloadJob> {
+        let idx = self
+            .queued
+            .iter()
+            .position(|job| job.transfer_id == transfer_id)?;
+        self.queued.remove(idx)
+    }
+}
+
+/// Cleans a file name taken from a remote link: only the last path segment
+/// is kept, control and reserved characters are dropped, and leading dots
+/// are stripped so a page can't plant hidden files.
+pub fn sanitize_filename(name: &str) -> String {
+    let last = name.rsplit(['/', '\\']).next().unwrap_or("");
+    let cleaned: String = last
+        .chars()
+        .filter(|c| !c.is_control() && !RESERVED_CHARS.contains(c))
+        .collect();
+    let cleaned = cleaned
+        .trim()
+        .trim_start_matches('.')
+        .trim_end_matches(['.', ' ']);
+
+    if cleaned.is_empty() {
+        "download".to_string()
+    } else {
+        truncate_name(cleaned)
+    }
+}
+
+/// Checks a name typed into the download modal. Separators and dot
+/// segments are refused rather than stripped so the user sees why.
+pub fn check_filename(name: &str) -> Result<String, TargetError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(TargetError::Empty);
+    }
+    if name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(TargetError::Traversal(name.to_string()));
+    }
+    Ok(sanitize_filename(name))
+}
+
+/// Where `filename` would land in `dir` without overwriting anything,
+/// numbering it `name (1).ext`, `name (2).ext`, ... on conflict.
+pub fn unique_path(dir: &Path, filename: &str) -> PathBuf {
+    let candidate = dir.join(filename);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, ext) = match filename.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (filename, None),
+    };
+    let mut n = 1;
+    loop {
+        let name = match ext {
+            Some(ext) => format!("{} ({}).{}", stem, n, ext),
+            None => format!("{} ({})", stem, n),
+        };
+        let candidate = dir.join(name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+fn truncate_name(name: &str) -> String {
+    if name.chars().count() <= MAX_NAME_CHARS {
+        return name.to_string();
+    }
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if ext.chars().count() < 16 => {
+            let keep = MAX_NAME_CHARS - ext.chars().count() - 1;
+            format!("{}.{}", stem.chars().take(keep).collect::<String>(), ext)
+        }
+        _ => name.chars().take(MAX_NAME_CHARS).collect(),
+    }
+}
+
+/// Opens `dir` in the system file manager.
+pub fn open_folder(dir: &Path) -> std::io::Result<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    Command::new(program)
+        .arg(dir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(transfer_id: u64) -> DownloadJob {
+        DownloadJob {
+            transfer_id,
+            node: NodeInfo {
+                hash: [0; 16],
+                name: "node".into(),
+                identify: false,
+            },
+            path: "/file/a.bin".into(),
+            filename: "a.bin".into(),
+            dir: PathBuf::from(DOWNLOADS_DIR),
+        }
+    }
+
+    #[test]
+    fn test_one_active_at_a_time() {
+        let mut queue = DownloadQueue::new();
+        queue.push(job(1));
+        queue.push(job(2));
+        queue.push(job(3));
+
+        assert_eq!(queue.start_next().map(|j| j.transfer_id), Some(1));
+        assert!(queue.start_next().is_none());
+        assert_eq!(queue.active(), Some(1));
+
+        queue.cancel(2);
+        queue.finish(1);
+        assert_eq!(queue.start_next().map(|j| j.transfer_id), Some(3));
+
+        queue.cancel(3);
+        assert_eq!(queue.active(), Some(3));
+        assert!(queue.start_next().is_none());
+        queue.finish(3);
+        assert_eq!(queue.active(), None);
+    }
+
+    #[test]
+    fn test_progress_counts_chunks_on_disk() {
+        let mut queue = DownloadQueue::new();
+        queue.push(job(1));
+        queue.start_next();
+
+        assert_eq!(queue.progress(10, 100), None);
+        queue.set_fetching(1, true);
+        assert_eq!(queue.progress(10, 100), Some((1, 10, 100)));
+        queue.set_offset(1, CHUNK_SIZE, Some(CHUNK_SIZE * 3));
+        assert_eq!(
+            queue.progress(5, 100),
+            Some((1, CHUNK_SIZE + 5, CHUNK_SIZE * 3))
+        );
+        queue.set_offset(2, 0, None);
+        assert_eq!(queue.progress(0, 0), Some((1, CHUNK_SIZE, CHUNK_SIZE * 3)));
+    }
+
+    #[test]
+    fn test_part_path_depends_on_source() {
+        let a = job(1);
+        let mut b = job(2);
+        assert_eq!(a.part_path(), b.part_path());
+        b.path = "/file/other/a.bin".into();
+        assert_ne!(a.part_path(), b.part_path());
+        let name = a
+            .part_path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        assert!(
+            name.starts_with("a.bin.") && name.ends_with(".part"),
+            "{}",
+            name
+        );
+    }
+
+    #[test]
+    fn test_chunk_round_trip() {
+        let chunk = FileChunk {
+            offset: 512,
+            total: 2048,
+            data: vec![1, 2, 3],
+        };
+        assert_eq!(FileChunk::decode(&chunk.encode()), Some(chunk));
+        assert_eq!(FileChunk::decode(b"%PDF-1.7 whole file"), None);
+
+        let request = range_request(512, CHUNK_SIZE);
+        assert_eq!(parse_range(&request[RANGE_KEY]), Some((512, CHUNK_SIZE)));
+        assert_eq!(parse_range("512"), None);
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("/file/report.pdf"), "report.pdf");
+        assert_eq!(sanitize_filename("/file/.."), "download");
+        assert_eq!(sanitize_filename("..\\..\\evil.exe"), "evil.exe");
+        assert_eq!(sanitize_filename(".bashrc"), "bashrc");
+        assert_eq!(sanitize_filename("a\u{1b}[31mb?.txt"), "a[31mb.txt");
+        assert_eq!(sanitize_filename("notes. "), "notes");
+
+        let long = format!("{}.tar", "x".repeat(300));
+        let truncated = sanitize_filename(&long);
+        assert_eq!(truncated.chars().count(), MAX_NAME_CHARS);
+        assert!(truncated.ends_with(".tar"));
+
+        assert!(matches!(
+            check_filename("../etc/passwd"),
+            Err(TargetError::Traversal(_))
+        ));
+        assert!(matches!(check_filename("  "), Err(TargetError::Empty)));
+        assert_eq!(check_filename(" my file.pdf ").unwrap(), "my file.pdf");
+    }
+
+    #[test]
+    fn test_unique_path_numbers_conflicts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        assert_eq!(unique_path(dir, "file.pdf"), dir.join("file.pdf"));
+        std::fs::write(dir.join("file.pdf"), b"").unwrap();
+        assert_eq!(unique_path(dir, "file.pdf"), dir.join("file (1).pdf"));
+        std::fs::write(dir.join("file (1).pdf"), b"").unwrap();
+        assert_eq!(unique_path(dir, "file.pdf"), dir.join("file (2).pdf"));
+
+        std::fs::write(dir.join("README"), b"").unwrap();
+        assert_eq!(unique_path(dir, "README"), dir.join("README (1)"));
+    }
+}
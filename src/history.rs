@@ -0,0 +1,171 @@
+use crate::network::{split_page_url, NodeInfo};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const HISTORY_PATH: &str = ".rinse/history.toml";
+
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub node_name: String,
+    pub visited_at: i64,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(url: String, node_name: String, content: &str) -> Self {
+        Self {
+            url,
+            node_name,
+            visited_at: chrono::Utc::now().timestamp(),
+            title: page_title(content),
+        }
+    }
+
+    /// Splits a `hash:/path` URL back into the node and path it was fetched
+    /// from.
+    pub fn target(&self) -> Option<(NodeInfo, String)> {
+        let (hash, path) = split_page_url(&self.url)?;
+        Some((
+            NodeInfo {
+                hash,
+                name: self.node_name.clone(),
+                identify: false,
+            },
+            path.to_string(),
+        ))
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct HistoryFile {
+    entries: Vec<HistoryEntry>,
+}
+
+/// Every page we've successfully loaded, oldest first. Visits are kept in
+/// memory and written out by `flush`, so browsing doesn't rewrite the file
+/// on every page.
+pub struct HistoryStore {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+    dirty: bool,
+}
+
+impl HistoryStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| toml::from_str::<HistoryFile>(&c).ok())
+            .map(|f| f.entries)
+            .unwrap_or_default();
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn record(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+        }
+        self.dirty = true;
+    }
+
+    /// Writes the file if anything was recorded since the last flush.
+    pub fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let file = HistoryFile {
+            entries: self.entries.clone(),
+        };
+
+        if let Ok(contents) = toml::to_string_pretty(&file) {
+            if fs::write(&self.path, contents).is_ok() {
+                self.dirty = false;
+            }
+        }
+    }
+}
+
+/// The text of the first micron heading (`>`, `>>`, ...) with formatting
+/// tags removed.
+pub fn page_title(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix('>'))
+        .map(|heading| strip_formatting(heading.trim_start_matches('>')))
+        .map(|title| title.trim().to_string())
+        .find(|title| !title.is_empty())
+}
+
+fn strip_formatting(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '`' {
+            out.push(c);
+            continue;
+        }
+        // Colour tags carry a three digit hex value, every other tag is one char.
+        if let Some('F' | 'B') = chars.next() {
+            chars.by_ref().take(3).for_each(drop);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_title() {
+        let page = "#!c=0\n`c`!Welcome`!\n\n>`F0afMy `!Node`!`f\n>>Sub\n";
+        assert_eq!(page_title(page).as_deref(), Some("My Node"));
+        assert_eq!(page_title(">>  \n>> Second").as_deref(), Some("Second"));
+        assert_eq!(page_title("no headings here"), None);
+    }
+
+    #[test]
+    fn test_entry_target() {
+        let hash = [0xab; 16];
+        let entry = HistoryEntry {
+            url: format!("{}:/page/about.mu", hex::encode(hash)),
+            node_name: "Node".into(),
+            visited_at: 0,
+            title: None,
+        };
+        let (node, path) = entry.target().unwrap();
+        assert_eq!(node.hash, hash);
+        assert_eq!(node.name, "Node");
+        assert_eq!(path, "/page/about.mu");
+    }
+
+    #[test]
+    fn test_visits_written_on_flush() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("history.toml");
+        let mut store = HistoryStore::new(&path);
+        store.record(HistoryEntry::new("a:/page/index.mu".into(), "A".into(), ""));
+        let written_early = path.exists();
+        store.flush();
+        let reloaded = HistoryStore::new(&path);
+
+        assert!(!written_early);
+        assert_eq!(reloaded.entries().len(), 1);
+        assert_eq!(reloaded.entries()[0].node_name, "A");
+    }
+}
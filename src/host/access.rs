@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+pub const ALLOW_LIST_SUFFIX: &str = ".allowed";
+
+/// Decides whether a remote identity may read a hosted resource.
+///
+/// A resource is restricted when `<name>.allowed` sits next to it, or when a
+/// directory between it and the root contains a bare `.allowed` file. Each
+/// allow list holds one hex identity hash per line. Blocked identities are
+/// refused everywhere.
+pub struct AccessControl {
+    blocked: HashSet<[u8; 16]>,
+}
+
+impl AccessControl {
+    pub fn new(blocked: &[String]) -> Self {
+        let blocked = blocked.iter().filter_map(|h| parse_identity(h)).collect();
+        Self { blocked }
+    }
+
+    pub fn is_allowed(&self, root: &Path, resource: &Path, remote: Option<[u8; 16]>) -> bool {
+        if let Some(identity) = remote {
+            if self.blocked.contains(&identity) {
+                return false;
+            }
+        }
+
+        match find_allow_list(root, resource) {
+            Some(allowed) => remote.is_some_and(|identity| allowed.contains(&identity)),
+            None => true,
+        }
+    }
+}
+
+/// Allow lists are configuration, never content.
+pub fn is_allow_list(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(ALLOW_LIST_SUFFIX))
+}
+
+fn find_allow_list(root: &Path, resource: &Path) -> Option<HashSet<[u8; 16]>> {
+    let mut own = resource.as_os_str().to_owned();
+    own.push(ALLOW_LIST_SUFFIX);
+    if let Some(list) = read_allow_list(Path::new(&own)) {
+        return Some(list);
+    }
+
+    let mut dir = resource.parent();
+    while let Some(d) = dir {
+        if let Some(list) = read_allow_list(&d.join(ALLOW_LIST_SUFFIX)) {
+            return Some(list);
+        }
+        if d == root {
+            break;
+        }
+        dir = d.parent();
+    }
+    None
+}
+
+fn read_allow_list(path: &Path) -> Option<HashSet<[u8; 16]>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    Some(
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(parse_identity)
+            .collect(),
+    )
+}
+
+fn parse_identity(hex_str: &str) -> Option<[u8; 16]> {
+    let bytes = hex::decode(hex_str.trim()).ok()?;
+    bytes.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allow_lists_and_blocklist() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let private = root.join("private");
+        std::fs::create_dir_all(&private).unwrap();
+
+        let friend = [0x11; 16];
+        let stranger = [0x22; 16];
+        let blocked = [0x33; 16];
+
+        std::fs::write(
+            private.join(".allowed"),
+            format!("# friends\n{}\n", hex::encode(friend)),
+        )
+        .unwrap();
+        std::fs::write(root.join("secret.mu.allowed"), hex::encode(stranger)).unwrap();
+
+        let acl = AccessControl::new(&[hex::encode(blocked)]);
+        let public = root.join("index.mu");
+        let board = private.join("board.mu");
+        let secret = root.join("secret.mu");
+
+        assert!(acl.is_allowed(root, &public, None));
+        assert!(!acl.is_allowed(root, &public, Some(blocked)));
+
+        assert!(acl.is_allowed(root, &board, Some(friend)));
+        assert!(!acl.is_allowed(root, &board, Some(stranger)));
+        assert!(!acl.is_allowed(root, &board, None));
+
+        assert!(acl.is_allowed(root, &secret, Some(stranger)));
+        assert!(!acl.is_allowed(root, &secret, Some(friend)));
+
+        assert!(is_allow_list(&private.join(".allowed")));
+        assert!(!is_allow_list(&board));
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+
+#[derive(Error, Debug)]
+pub enum ScriptError {
+    #[error("failed to start: {0}")]
+    Spawn(std::io::Error),
+    #[error("timed out after {0}s")]
+    Timeout(u64),
+    #[error("output exceeded {0} bytes")]
+    OutputTooLarge(usize),
+    #[error("exited with {0}")]
+    Failed(std::process::ExitStatus),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Decodes the msgpack form map sent by `build_page_request`. Anything that
+/// isn't a plain string map is treated as no form data.
+pub fn parse_request_data(data: &[u8]) -> HashMap<String, String> {
+    if data.is_empty() {
+        return HashMap::new();
+    }
+    rmp_serde::from_slice(data).unwrap_or_default()
+}
+
+/// Runs an executable page the way NomadNet does: `field_*` and `var_*`
+/// entries become environment variables, as does `remote_identity` when the
+/// peer has identified.
+pub async fn run_script(
+    path: &Path,
+    form_data: &HashMap<String, String>,
+    remote_identity: Option<[u8; 16]>,
+    timeout: Duration,
+    max_output: usize,
+) -> Result<String, ScriptError> {
+    let mut cmd = Command::new(path);
+    if let Some(dir) = path.parent() {
+        cmd.current_dir(dir);
+    }
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+
+    for (key, value) in form_data {
+        if is_form_env_key(key) {
+            cmd.env(key, value);
+        }
+    }
+    if let Some(identity) = remote_identity {
+        cmd.env("remote_identity", hex::encode(identity));
+    }
+
+    let mut child = cmd.spawn().map_err(ScriptError::Spawn)?;
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+
+    let run = async {
+        let mut output = Vec::new();
+        (&mut stdout)
+            .take(max_output as u64 + 1)
+            .read_to_end(&mut output)
+            .await?;
+        if output.len() > max_output {
+            return Err(ScriptError::OutputTooLarge(max_output));
+        }
+        let status = child.wait().await?;
+        if !status.success() {
+            return Err(ScriptError::Failed(status));
+        }
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    };
+
+    match tokio::time::timeout(timeout, run).await {
+        Ok(result) => result,
+        Err(_) => Err(ScriptError::Timeout(timeout.as_secs())),
+    }
+}
+
+fn is_form_env_key(key: &str) -> bool {
+    (key.starts_with("field_") || key.starts_with("var_"))
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+
+    /// Writes an executable script, closing it before it's run: executing a
+    /// file that is still open for writing fails with ETXTBSY.
+    fn write_script(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let path = dir.join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(body.as_bytes()).unwrap();
+        file.set_permissions(std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+        file.sync_all().unwrap();
+        drop(file);
+        path
+    }
+
+    #[tokio::test]
+    async fn test_script_receives_form_and_identity() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = write_script(
+            dir,
+            "echo.mu",
+            "#!/bin/sh\necho \"$field_name $var_page $remote_identity $field_bad\"\n",
+        );
+        let mut form = HashMap::new();
+        form.insert("field_name".to_string(), "alice".to_string());
+        form.insert("var_page".to_string(), "2".to_string());
+        form.insert("field_bad;rm".to_string(), "x".to_string());
+
+        let out = run_script(&path, &form, Some([0xab; 16]), Duration::from_secs(5), 1024)
+            .await
+            .unwrap();
+        assert_eq!(out.trim(), format!("alice 2 {}", hex::encode([0xab; 16])));
+    }
+
+    #[tokio::test]
+    async fn test_script_limits() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let slow = write_script(dir, "slow.mu", "#!/bin/sh\nsleep 5\n");
+        let result = run_script(
+            &slow,
+            &HashMap::new(),
+            None,
+            Duration::from_millis(200),
+            1024,
+        )
+        .await;
+        assert!(matches!(result, Err(ScriptError::Timeout(_))));
+
+        let noisy = write_script(dir, "noisy.mu", "#!/bin/sh\nhead -c 4096 /dev/zero\n");
+        let result = run_script(&noisy, &HashMap::new(), None, Duration::from_secs(5), 1024).await;
+        assert!(matches!(result, Err(ScriptError::OutputTooLarge(1024))));
+
+        let failing = write_script(dir, "fail.mu", "#!/bin/sh\nexit 3\n");
+        let result = run_script(
+            &failing,
+            &HashMap::new(),
+            None,
+            Duration::from_secs(5),
+            1024,
+        )
+        .await;
+        assert!(matches!(result, Err(ScriptError::Failed(_))));
+    }
+}
@@ -0,0 +1,5 @@
+mod access;
+mod dynamic;
+mod page_host;
+
+pub use page_host::PageHost;
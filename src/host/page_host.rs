@@ -0,0 +1,290 @@
+use super::access::{is_allow_list, AccessControl};
+use super::dynamic::{is_executable, parse_request_data, run_script};
+use crate::downloads::{parse_range, FileChunk, MAX_CHUNK_SIZE, RANGE_KEY};
+use crate::settings::HostingSettings;
+use serde_bytes::ByteBuf;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const PAGE_PREFIX: &str = "/page/";
+const FILE_PREFIX: &str = "/file/";
+const INDEX_PATH: &str = "/page/index.mu";
+
+const DEFAULT_INDEX: &str = "\
+>Nomad Node
+
+This node is running nomad but hasn't published any pages yet.
+
+If this is your node, put micron pages in the pages directory configured in `!.rinse/nomad.toml`! \
+starting with `!index.mu`!.
+";
+
+const DEFAULT_ACCESS_DENIED: &str = "\
+>Access Denied
+
+You don't have access to this page.
+
+If you've been granted access, enable `!ID`! for this node and reload so it knows who you are.
+";
+
+pub struct PageHost {
+    pages_dir: PathBuf,
+    files_dir: PathBuf,
+    script_timeout: Duration,
+    max_script_output: usize,
+    access: AccessControl,
+    access_denied_page: Option<PathBuf>,
+}
+
+impl PageHost {
+    pub fn new(settings: &HostingSettings) -> Self {
+        let [pages_dir, files_dir] = [&settings.pages_dir, &settings.files_dir].map(|dir| {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                log::warn!("Failed to create {}: {}", dir.display(), e);
+            }
+            dir.canonicalize().unwrap_or_else(|_| dir.clone())
+        });
+
+        Self {
+            pages_dir,
+            files_dir,
+            script_timeout: Duration::from_secs(settings.script_timeout_secs),
+            max_script_output: settings.max_script_output,
+            access: AccessControl::new(&settings.blocked_identities),
+            access_denied_page: settings.access_denied_page.clone(),
+        }
+    }
+
+    pub async fn handle(
+        &self,
+        path: &str,
+        data: &[u8],
+        remote_identity: Option<[u8; 16]>,
+    ) -> Vec<u8> {
+        if let Some(rel) = path.strip_prefix(PAGE_PREFIX) {
+            return self.serve_page(path, rel, data, remote_identity).await;
+        }
+
+        if let Some(rel) = path.strip_prefix(FILE_PREFIX) {
+            if let Some(file_path) = self.resolve(&self.files_dir, rel) {
+                if !self
+                    .access
+                    .is_allowed(&self.files_dir, &file_path, remote_identity)
+                {
+                    return self.access_denied(path, remote_identity).await;
+                }
+                let range = parse_request_data(data)
+                    .get(RANGE_KEY)
+                    .and_then(|value| parse_range(value));
+                if let Some((offset, len)) = range {
+                    match read_chunk(&file_path, offset, len).await {
+                        Ok(chunk) => {
+                            log::info!(
+                                "Serving {} bytes of {:?} at {}",
+                                chunk.data.len(),
+                                file_path,
+                                offset
+                            );
+                            return chunk.encode();
+                        }
+                        Err(e) => log::warn!("Failed to read {:?}: {}", file_path, e),
+                    }
+                }
+                match tokio::fs::read(&file_path).await {
+                    Ok(data) => {
+                        log::info!("Serving file {:?} ({} bytes)", file_path, data.len());
+                        return data;
+                    }
+                    Err(e) => log::warn!("Failed to read {:?}: {}", file_path, e),
+                }
+            }
+        }
+
+        log::info!("Requested path not found: {}", path);
+        build_page_response(path, &not_found_page(path))
+    }
+
+    async fn serve_page(
+        &self,
+        path: &str,
+        rel: &str,
+        data: &[u8],
+        remote_identity: Option<[u8; 16]>,
+    ) -> Vec<u8> {
+        let page_path = self.resolve(&self.pages_dir, rel);
+        if let Some(page_path) = &page_path {
+            if !self
+                .access
+                .is_allowed(&self.pages_dir, page_path, remote_identity)
+            {
+                return self.access_denied(path, remote_identity).await;
+            }
+        }
+
+        let content = match page_path {
+            Some(page_path) if is_executable(&page_path) => {
+                log::info!("Running dynamic page {}", path);
+                let form_data = parse_request_data(data);
+                match run_script(
+                    &page_path,
+                    &form_data,
+                    remote_identity,
+                    self.script_timeout,
+                    self.max_script_output,
+                )
+                .await
+                {
+                    Ok(output) => Some(output),
+                    Err(e) => {
+                        log::warn!("Dynamic page {} failed: {}", path, e);
+                        return build_page_response(
+                            path,
+                            &error_page(
+                                "Page Error",
+                                "This page could not be generated. Try again later.",
+                            ),
+                        );
+                    }
+                }
+            }
+            Some(page_path) => tokio::fs::read_to_string(&page_path).await.ok(),
+            None => None,
+        };
+
+        match content {
+            Some(content) => {
+                log::info!("Serving page {}", path);
+                build_page_response(path, &content)
+            }
+            None if path == INDEX_PATH => build_page_response(path, DEFAULT_INDEX),
+            None => {
+                log::info!("Requested page not found: {}", path);
+                build_page_response(path, &not_found_page(path))
+            }
+        }
+    }
+
+    fn resolve(&self, root: &Path, rel: &str) -> Option<PathBuf> {
+        resolve_under(root, rel).filter(|p| !is_allow_list(p))
+    }
+
+    async fn access_denied(&self, path: &str, remote_identity: Option<[u8; 16]>) -> Vec<u8> {
+        log::info!(
+            "Denied {} to {}",
+            path,
+            remote_identity
+                .map(hex::encode)
+                .unwrap_or_else(|| "unidentified peer".to_string())
+        );
+
+        let custom = match &self.access_denied_page {
+            Some(page) => tokio::fs::read_to_string(page).await.ok(),
+            None => None,
+        };
+        build_page_response(path, custom.as_deref().unwrap_or(DEFAULT_ACCESS_DENIED))
+    }
+}
+
+async fn read_chunk(path: &Path, offset: u64, len: u64) -> std::io::Result<FileChunk> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let total = file.metadata().await?.len();
+    let offset = offset.min(total);
+    file.seek(std::io::SeekFrom::Start(offset)).await?;
+
+    let mut data = Vec::new();
+    file.take(len.min(MAX_CHUNK_SIZE))
+        .read_to_end(&mut data)
+        .await?;
+    Ok(FileChunk {
+        offset,
+        total,
+        data,
+    })
+}
+
+/// Joins `rel` onto `root`, refusing anything that could step outside of it.
+fn resolve_under(root: &Path, rel: &str) -> Option<PathBuf> {
+    let rel = Path::new(rel);
+    if rel.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return None;
+    }
+
+    let root = root.canonicalize().ok()?;
+    let full = root.join(rel).canonicalize().ok()?;
+    if full.starts_with(&root) && full.is_file() {
+        Some(full)
+    } else {
+        None
+    }
+}
+
+/// Encodes a page the same way NomadNet nodes do: `(timestamp, path, content)`.
+pub(crate) fn build_page_response(path: &str, content: &str) -> Vec<u8> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0);
+
+    let response = (
+        timestamp,
+        ByteBuf::from(path.as_bytes().to_vec()),
+        Some(ByteBuf::from(content.as_bytes().to_vec())),
+    );
+    rmp_serde::to_vec(&response).unwrap_or_default()
+}
+
+pub(crate) fn error_page(title: &str, message: &str) -> String {
+    format!(">{}\n\n{}\n", title, message)
+}
+
+fn not_found_page(path: &str) -> String {
+    error_page(
+        "Not Found",
+        &format!(
+            "The requested path `!{}`! does not exist on this node.",
+            path
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::page_source::PageEnvelope;
+
+    #[test]
+    fn test_resolve_rejects_traversal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join("index.mu"), "hello").unwrap();
+
+        assert!(resolve_under(root, "index.mu").is_some());
+        assert!(resolve_under(root, "../index.mu").is_none());
+        assert!(resolve_under(root, "/etc/passwd").is_none());
+        assert!(resolve_under(root, "missing.mu").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_chunk_clamps_to_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file = root.join("data.bin");
+        std::fs::write(&file, b"0123456789").unwrap();
+
+        let chunk = read_chunk(&file, 4, 3).await.unwrap();
+        assert_eq!((chunk.offset, chunk.total), (4, 10));
+        assert_eq!(chunk.data, b"456");
+        assert_eq!(read_chunk(&file, 8, 100).await.unwrap().data, b"89");
+        assert!(read_chunk(&file, 20, 5).await.unwrap().data.is_empty());
+    }
+
+    #[test]
+    fn test_page_response_matches_client_shape() {
+        let data = build_page_response("/page/index.mu", "`!hi");
+        let envelope = PageEnvelope::decode(&data);
+        assert!(envelope.timestamp.is_some());
+        assert_eq!(envelope.path.as_deref(), Some("/page/index.mu"));
+        assert_eq!(envelope.content, "`!hi");
+    }
+}
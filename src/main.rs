@@ -1,65 +1,116 @@
 mod app;
+mod bookmarks;
+mod cache;
+mod config_file;
+mod downloads;
+mod history;
+mod host;
 mod identity;
+mod messages;
 mod network;
+mod settings;
 mod tui;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use rinse::config::{save_ratchets, Config, InterfaceConfig};
-use rinse::{Interface, RequestError, TcpTransport};
+use rinse::{RequestError, ServiceId, TcpTransport};
 
-use tokio::sync::{mpsc, oneshot};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio_util::sync::CancellationToken;
 
 use app::NomadApp;
-use network::{NetworkClient, NodeRegistry};
+use bookmarks::{BookmarkStore, BOOKMARKS_PATH};
+use cache::PageCache;
+use downloads::{range_request, unique_path, DownloadJob, DownloadQueue, FileChunk, CHUNK_SIZE};
+use history::{HistoryStore, HISTORY_PATH};
+use host::PageHost;
+use messages::{ConversationStore, DirectMessage, MessageState, StoredMessage, CONVERSATIONS_DIR};
+use network::supervisor::{self, Backoff, LinkState};
+use network::traffic::{InterfaceStats, Traffic};
+use network::{
+    bridge, tcp_server, DiscoveryStore, LinkPool, NetworkClient, NodeRegistry, DISCOVERY_PATH,
+};
+use settings::Settings;
 use tui::{InterfaceInfo, InterfaceKind, NetworkEvent, TuiApp, TuiCommand};
 
 struct FetchReq {
+    service: ServiceId,
     dest: [u8; 16],
     path: String,
-    form_data: HashMap<String, String>,
+    data: Vec<u8>,
     identify: bool,
     reply: oneshot::Sender<Result<Vec<u8>, String>>,
+    /// Stops the request, replying once that's done. Requests without one
+    /// are cancelled by dropping the reply receiver.
+    cancel: Option<CancellationToken>,
 }
 
 enum InternalCmd {
     Fetch(FetchReq),
     GetStats(oneshot::Sender<rinse::StatsSnapshot>),
+    DownloadFinished(u64),
+    MessageReceived(DirectMessage),
+    MessageSent {
+        peer: [u8; 16],
+        id: String,
+        result: Result<MessageState, String>,
+    },
 }
 
+/// Every configured interface, disabled ones included, sorted by name.
 fn build_interface_info(config: &Config, status: &HashMap<String, bool>) -> Vec<InterfaceInfo> {
-    config
-        .enabled_interfaces()
+    let mut interfaces: Vec<InterfaceInfo> = config
+        .interfaces
         .iter()
         .map(|(name, iface_config)| {
-            let (kind, address) = match iface_config {
+            let (kind, address, enabled) = match iface_config {
                 InterfaceConfig::TCPClientInterface {
+                    enabled,
                     target_host,
                     target_port,
-                    ..
                 } => (
                     InterfaceKind::TcpClient,
-                    format!("{}:{}", target_host, target_port),
+                    config_file::interface_address(target_host, *target_port),
+                    *enabled,
                 ),
                 InterfaceConfig::TCPServerInterface {
+                    enabled,
                     listen_ip,
                     listen_port,
-                    ..
                 } => (
                     InterfaceKind::TcpServer,
-                    format!("{}:{}", listen_ip, listen_port),
+                    config_file::interface_address(listen_ip, *listen_port),
+                    *enabled,
                 ),
             };
-            let connected = status.get(*name).copied().unwrap_or(false);
+            let connected = status.get(name).copied().unwrap_or(false);
             InterfaceInfo {
-                name: name.to_string(),
+                name: name.clone(),
                 kind,
                 address,
+                enabled,
                 connected,
+                clients: Vec::new(),
+                retry: None,
+                stats: InterfaceStats::default(),
             }
         })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+fn enabled_interface_configs(config: &Config) -> HashMap<String, InterfaceConfig> {
+    config
+        .enabled_interfaces()
+        .into_iter()
+        .map(|(name, cfg)| (name.to_string(), cfg.clone()))
         .collect()
 }
 
@@ -87,18 +138,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     log::info!("Starting Nomad...");
 
     let config = Config::load()?;
-    let interface_configs: HashMap<String, InterfaceConfig> = config
-        .enabled_interfaces()
-        .into_iter()
-        .map(|(name, cfg)| (name.to_string(), cfg.clone()))
-        .collect();
+    let settings = Settings::load();
+    let mut interface_configs = enabled_interface_configs(&config);
 
     let (
         node,
         service_id,
         dest_hash,
+        message_service_id,
+        message_address,
         relay_enabled,
         interface_info,
+        listeners,
         identity,
         announced_on_startup,
     ) = {
@@ -107,15 +158,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let relay_enabled = nomad.relay_enabled();
         let interface_info = build_interface_info(&config, nomad.interface_status());
         let announced_on_startup = nomad.announced_on_startup();
+        let listeners = nomad.take_listeners();
         let node = nomad.take_node();
         let service_id = nomad.take_service_id();
+        let message_address = nomad.message_address();
+        let message_service_id = nomad.take_message_service_id();
         let identity = nomad.take_identity();
         (
             node,
             service_id,
             dest_hash,
+            message_service_id,
+            message_address,
             relay_enabled,
             interface_info,
+            listeners,
             identity,
             announced_on_startup,
         )
@@ -126,13 +183,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
     let (internal_tx, mut internal_rx) = mpsc::channel::<InternalCmd>(32);
 
-    let registry = NodeRegistry::new(".rinse/nodes.toml");
-    let initial_nodes: Vec<_> = registry.all().into_iter().cloned().collect();
-    let network_client = Arc::new(NetworkClient::new(registry));
+    let mut bookmarks = BookmarkStore::new(BOOKMARKS_PATH);
+    let initial_bookmarks = bookmarks.all().to_vec();
+    let bookmarked: HashSet<_> = initial_bookmarks
+        .iter()
+        .filter_map(|b| b.node_hash())
+        .collect();
+    let mut registry = NodeRegistry::new(".rinse/nodes.toml", &bookmarked);
+    let initial_nodes: Vec<_> = registry.saved().into_iter().cloned().collect();
+    let mut discovered = DiscoveryStore::new(DISCOVERY_PATH);
+    discovered.import(registry.take_unsaved());
+    let initial_discovered = discovered.all();
+    let network_client = Arc::new(NetworkClient::new(registry, discovered));
+
+    let mut history = HistoryStore::new(HISTORY_PATH);
+    let initial_history = history.entries().to_vec();
+    let mut conversations = ConversationStore::new(CONVERSATIONS_DIR);
+    let initial_conversations = conversations.all().to_vec();
 
     let network_client_clone = network_client.clone();
     let event_tx_clone = event_tx.clone();
     let internal_tx_stats = internal_tx.clone();
+    let internal_tx_messages = internal_tx.clone();
 
     let node_for_run = Arc::try_unwrap(node)
         .ok()
@@ -146,6 +218,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let node = Arc::new(node_clone);
     let node_for_network = node.clone();
+    let link_pool = Arc::new(Mutex::new(LinkPool::new(std::time::Duration::from_secs(
+        settings.links.idle_timeout_secs,
+    ))));
+    let link_pool_for_sweep = link_pool.clone();
+    let page_cache = Arc::new(PageCache::new(&settings.cache.dir));
+    page_cache.prune(std::time::Duration::from_secs(
+        settings.cache.max_age_days * 24 * 60 * 60,
+    ));
+    let downloads = Arc::new(Mutex::new(DownloadQueue::new()));
+    let downloads_for_progress = downloads.clone();
+    // Requests on the wire; progress is only attributable when there's one.
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let fetches_for_progress = fetches.clone();
 
     let network_task = tokio::spawn(async move {
         let node = node_for_network;
@@ -157,18 +242,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        let mut in_flight: HashMap<u64, tokio::task::AbortHandle> = HashMap::new();
+        let mut downloading: HashMap<u64, DownloadTask> = HashMap::new();
+        let services = [service_id, message_service_id];
+        // Counters outlive restarts, so reconnects add up; edits reset them.
+        let mut traffic: HashMap<String, Arc<Traffic>> = HashMap::new();
+        let mut running: HashMap<String, tokio::task::JoinHandle<()>> = listeners
+            .into_iter()
+            .map(|(name, listener)| {
+                let handle = spawn_tcp_server(
+                    name.clone(),
+                    listener,
+                    (*node).clone(),
+                    traffic.entry(name.clone()).or_default().clone(),
+                    event_tx_clone.clone(),
+                );
+                (name, handle)
+            })
+            .collect();
+        for (name, cfg) in &interface_configs {
+            if let InterfaceConfig::TCPClientInterface { .. } = cfg {
+                let counters = traffic.entry(name.clone()).or_default().clone();
+                if let Some(handle) =
+                    start_interface(name, cfg, &node, services, counters, &event_tx_clone).await
+                {
+                    running.insert(name.clone(), handle);
+                }
+            }
+        }
+        let mut history_flush = tokio::time::interval(HISTORY_FLUSH_INTERVAL);
+
         loop {
             tokio::select! {
                 _ = shutdown_rx.recv() => {
                     log::info!("Shutdown signal received");
+                    running.values().for_each(|task| task.abort());
+                    history.flush();
                     break;
                 }
+                _ = history_flush.tick() => history.flush(),
                 Some(cmd) = cmd_rx.recv() => {
                     match cmd {
                         TuiCommand::Announce => {
                             let _ = event_tx_clone.send(NetworkEvent::Status("Announcing...".to_string())).await;
                             log::info!("Announce command received");
                             node.announce(service_id);
+                            node.announce(message_service_id);
                             log::info!("Announce completed");
 
                             if let Some(ratchets) = node.export_ratchets(service_id).await {
@@ -179,24 +298,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                             let _ = event_tx_clone.send(NetworkEvent::AnnounceSent).await;
                         }
-                        TuiCommand::FetchPage { node: target_node, path, form_data } => {
+                        TuiCommand::FetchPage { request_id, node: target_node, path, form_data } => {
                             log::info!("FetchPage command received: {} path={} form_data={:?}", target_node.hash_hex(), path, form_data);
                             let url = format!("{}:{}", target_node.hash_hex(), path);
                             let event_tx = event_tx_clone.clone();
                             let internal_tx = internal_tx.clone();
+                            let page_cache = page_cache.clone();
 
-                            tokio::spawn(async move {
+                            let handle = tokio::spawn(async move {
                                 log::info!("Spawned fetch task for {}", url);
+                                if let Some(cached) = page_cache.get(&url, &form_data).await {
+                                    let _ = event_tx.send(NetworkEvent::PageCached { request_id, url: url.clone(), data: cached.data, fetched_at: cached.fetched_at }).await;
+                                }
                                 let _ = event_tx.send(NetworkEvent::Status("Sending request...".into())).await;
 
                                 let (reply_tx, reply_rx) = oneshot::channel();
+                                let started = std::time::Instant::now();
                                 log::info!("Sending InternalCmd::Fetch");
                                 let _ = internal_tx.send(InternalCmd::Fetch(FetchReq {
+                                    service: service_id,
                                     dest: target_node.hash,
                                     path: path.clone(),
-                                    form_data,
+                                    data: build_page_request(&form_data),
                                     identify: target_node.identify,
                                     reply: reply_tx,
+                                    cancel: None,
                                 })).await;
                                 log::info!("InternalCmd::Fetch sent, waiting for reply");
 
@@ -204,152 +330,137 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 match reply_rx.await {
                                     Ok(Ok(data)) => {
-                                        let _ = event_tx.send(NetworkEvent::PageReceived { url, data }).await;
+                                        page_cache.put(&url, &form_data, &data).await;
+                                        let _ = event_tx.send(NetworkEvent::PageReceived { request_id, url, data, round_trip_ms: started.elapsed().as_millis() as u64 }).await;
                                     }
                                     Ok(Err(e)) => {
-                                        let _ = event_tx.send(NetworkEvent::PageFailed { url, reason: e }).await;
+                                        let _ = event_tx.send(NetworkEvent::PageFailed { request_id, url, reason: e }).await;
                                     }
                                     Err(_) => {
-                                        let _ = event_tx.send(NetworkEvent::PageFailed { url, reason: "Request cancelled".into() }).await;
+                                        let _ = event_tx.send(NetworkEvent::PageFailed { request_id, url, reason: "Request cancelled".into() }).await;
                                     }
                                 }
                             });
+                            in_flight.retain(|_, h| !h.is_finished());
+                            in_flight.insert(request_id, handle.abort_handle());
                         }
-                        TuiCommand::FetchPartial { node: target_node, partial, form_data } => {
+                        TuiCommand::FetchPartial { request_id, node: target_node, partial, form_data } => {
                             log::info!("FetchPartial command received: {} url={}", target_node.hash_hex(), partial.url);
                             let event_tx = event_tx_clone.clone();
                             let internal_tx = internal_tx.clone();
+                            let page_cache = page_cache.clone();
 
-                            tokio::spawn(async move {
-                                let (reply_tx, reply_rx) = oneshot::channel();
-                                let _ = internal_tx.send(InternalCmd::Fetch(FetchReq {
-                                    dest: target_node.hash,
-                                    path: partial.url.clone(),
-                                    form_data,
-                                    identify: target_node.identify,
-                                    reply: reply_tx,
-                                })).await;
-
-                                match reply_rx.await {
-                                    Ok(Ok(data)) => {
-                                        let _ = event_tx.send(NetworkEvent::PartialReceived { partial, data }).await;
-                                    }
-                                    Ok(Err(e)) => {
-                                        let _ = event_tx.send(NetworkEvent::PartialFailed { partial, reason: e }).await;
-                                    }
-                                    Err(_) => {
-                                        let _ = event_tx.send(NetworkEvent::PartialFailed { partial, reason: "Request cancelled".into() }).await;
-                                    }
+                            let handle = tokio::spawn(async move {
+                                let url = format!("{}:{}", target_node.hash_hex(), partial.url);
+                                if let Some(cached) = page_cache.get(&url, &form_data).await {
+                                    let _ = event_tx.send(NetworkEvent::PartialCached { request_id, partial: partial.clone(), data: cached.data }).await;
                                 }
-                            });
-                        }
-                        TuiCommand::DownloadFile { node: target_node, path, filename } => {
-                            log::info!("Download requested: {} from {} path={}", filename, target_node.name, path);
-                            let event_tx = event_tx_clone.clone();
-                            let internal_tx = internal_tx.clone();
-
-                            tokio::spawn(async move {
-                                let _ = event_tx.send(NetworkEvent::Status(format!("Downloading {}...", filename))).await;
 
                                 let (reply_tx, reply_rx) = oneshot::channel();
                                 let _ = internal_tx.send(InternalCmd::Fetch(FetchReq {
+                                    service: service_id,
                                     dest: target_node.hash,
-                                    path,
-                                    form_data: HashMap::new(),
+                                    path: partial.url.clone(),
+                                    data: build_page_request(&form_data),
                                     identify: target_node.identify,
                                     reply: reply_tx,
+                                    cancel: None,
                                 })).await;
 
                                 match reply_rx.await {
                                     Ok(Ok(data)) => {
-                                        let download_dir = std::path::Path::new(".rinse/downloads");
-                                        if let Err(e) = std::fs::create_dir_all(download_dir) {
-                                            let _ = event_tx.send(NetworkEvent::DownloadFailed {
-                                                filename,
-                                                reason: format!("Failed to create downloads dir: {}", e),
-                                            }).await;
-                                            return;
-                                        }
-
-                                        let file_path = download_dir.join(&filename);
-                                        log::info!("Writing {} bytes to {:?}", data.len(), file_path);
-                                        match std::fs::write(&file_path, &data) {
-                                            Ok(_) => {
-                                                log::info!("Download complete: {:?}", file_path);
-                                                let _ = event_tx.send(NetworkEvent::DownloadComplete {
-                                                    filename,
-                                                    path: file_path.display().to_string(),
-                                                }).await;
-                                            }
-                                            Err(e) => {
-                                                log::error!("Failed to write file: {}", e);
-                                                let _ = event_tx.send(NetworkEvent::DownloadFailed {
-                                                    filename,
-                                                    reason: format!("Failed to write file: {}", e),
-                                                }).await;
-                                            }
-                                        }
+                                        page_cache.put(&url, &form_data, &data).await;
+                                        let _ = event_tx.send(NetworkEvent::PartialReceived { request_id, partial, data }).await;
                                     }
                                     Ok(Err(e)) => {
-                                        log::error!("Download failed: {}", e);
-                                        let _ = event_tx.send(NetworkEvent::DownloadFailed {
-                                            filename,
-                                            reason: e,
-                                        }).await;
+                                        let _ = event_tx.send(NetworkEvent::PartialFailed { request_id, partial, reason: e }).await;
                                     }
                                     Err(_) => {
-                                        let _ = event_tx.send(NetworkEvent::DownloadFailed {
-                                            filename,
-                                            reason: "Request cancelled".into(),
-                                        }).await;
+                                        let _ = event_tx.send(NetworkEvent::PartialFailed { request_id, partial, reason: "Request cancelled".into() }).await;
                                     }
                                 }
                             });
+                            in_flight.retain(|_, h| !h.is_finished());
+                            in_flight.insert(request_id, handle.abort_handle());
+                        }
+                        TuiCommand::DownloadFile { request_id, node: target_node, path, filename, dir } => {
+                            log::info!("Download requested: {} from {} path={} into {:?}", filename, target_node.name, path, dir);
+                            let mut queue = downloads.lock().await;
+                            queue.push(DownloadJob { transfer_id: request_id, node: target_node, path, filename, dir });
+                            if let Some(job) = queue.start_next() {
+                                downloading.insert(job.transfer_id, spawn_download(job, service_id, downloads.clone(), event_tx_clone.clone(), internal_tx.clone()));
+                            }
+                        }
+                        TuiCommand::CancelRequest { request_id } => {
+                            if let Some(handle) = in_flight.remove(&request_id) {
+                                log::info!("Cancelling request {}", request_id);
+                                handle.abort();
+                            }
+                        }
+                        TuiCommand::CancelDownload { transfer_id, discard } => {
+                            log::info!("Cancelling download {} (discard={})", transfer_id, discard);
+                            // The next download starts once this one reports
+                            // DownloadFinished, after its link is closed.
+                            if let Some(task) = downloading.remove(&transfer_id) {
+                                task.cancel(discard);
+                            }
+                            let queued = downloads.lock().await.cancel(transfer_id);
+                            if let Some(job) = queued.filter(|_| discard) {
+                                remove_part(&job.part_path()).await;
+                            }
                         }
                         TuiCommand::Reconnect { name } => {
                             log::info!("Reconnect requested for interface: {}", name);
-                            if let Some(iface_config) = interface_configs.get(&name) {
-                                let addr = match iface_config {
-                                    InterfaceConfig::TCPClientInterface { target_host, target_port, .. } => {
-                                        format!("{}:{}", target_host, target_port)
+                            match interface_configs.get(&name) {
+                                Some(cfg) => {
+                                    // Restarting retries a client now and resets its backoff;
+                                    // a server tries binding its address again.
+                                    stop_interface(&mut running, &name).await;
+                                    let _ = event_tx_clone.send(NetworkEvent::Status(format!("Connecting to {}...", name))).await;
+                                    let counters = traffic.entry(name.clone()).or_default().clone();
+                                    if let Some(handle) = start_interface(&name, cfg, &node, services, counters, &event_tx_clone).await {
+                                        running.insert(name, handle);
                                     }
-                                    InterfaceConfig::TCPServerInterface { listen_ip, listen_port, .. } => {
-                                        format!("{}:{}", listen_ip, listen_port)
-                                    }
-                                };
-                                let event_tx = event_tx_clone.clone();
-                                let name_clone = name.clone();
-                                let node = node.clone();
-                                tokio::spawn(async move {
-                                    let _ = event_tx.send(NetworkEvent::Status(
-                                        format!("Connecting to {}...", name_clone)
-                                    )).await;
-                                    match TcpTransport::connect(&addr).await {
-                                        Ok(transport) => {
-                                            node.add_interface(Interface::new(transport));
-                                            log::info!("Reconnected to {}", name_clone);
-                                            let _ = event_tx.send(NetworkEvent::InterfaceStatus {
-                                                name: name_clone.clone(),
-                                                connected: true,
-                                            }).await;
-                                            let _ = event_tx.send(NetworkEvent::Status(
-                                                format!("Connected to {}", name_clone)
-                                            )).await;
-                                        }
-                                        Err(e) => {
-                                            log::warn!("Failed to reconnect to {}: {}", name_clone, e);
-                                            let _ = event_tx.send(NetworkEvent::InterfaceStatus {
-                                                name: name_clone.clone(),
-                                                connected: false,
-                                            }).await;
-                                            let _ = event_tx.send(NetworkEvent::Status(
-                                                format!("Failed to connect to {}: {}", name_clone, e)
-                                            )).await;
-                                        }
+                                }
+                                None => log::warn!("Unknown interface: {}", name),
+                            }
+                        }
+                        TuiCommand::SaveInterface { original, name, config: iface } => {
+                            log::info!("Saving interface: {}", name);
+                            let path = std::path::Path::new(config_file::CONFIG_PATH);
+                            if let Err(e) = config_file::save_interface(path, original.as_deref(), &name, &iface) {
+                                log::warn!("Failed to save interface {}: {}", name, e);
+                                let _ = event_tx_clone.send(NetworkEvent::Status(format!("Couldn't save {}: {}", name, e))).await;
+                                continue;
+                            }
+                            for old in original.iter().chain([&name]) {
+                                stop_interface(&mut running, old).await;
+                                traffic.remove(old);
+                            }
+                            if let Some(configs) = reload_interfaces(&event_tx_clone).await {
+                                interface_configs = configs;
+                                let _ = event_tx_clone.send(NetworkEvent::Status(format!("Saved {}", name))).await;
+                                if let Some(cfg) = interface_configs.get(&name) {
+                                    let counters = traffic.entry(name.clone()).or_default().clone();
+                                    if let Some(handle) = start_interface(&name, cfg, &node, services, counters, &event_tx_clone).await {
+                                        running.insert(name, handle);
                                     }
-                                });
-                            } else {
-                                log::warn!("Unknown interface: {}", name);
+                                }
+                            }
+                        }
+                        TuiCommand::DeleteInterface { name } => {
+                            log::info!("Deleting interface: {}", name);
+                            let path = std::path::Path::new(config_file::CONFIG_PATH);
+                            if let Err(e) = config_file::delete_interface(path, &name) {
+                                log::warn!("Failed to delete interface {}: {}", name, e);
+                                let _ = event_tx_clone.send(NetworkEvent::Status(format!("Couldn't remove {}: {}", name, e))).await;
+                                continue;
+                            }
+                            stop_interface(&mut running, &name).await;
+                            traffic.remove(&name);
+                            if let Some(configs) = reload_interfaces(&event_tx_clone).await {
+                                interface_configs = configs;
+                                let _ = event_tx_clone.send(NetworkEvent::Status(format!("Removed {}", name))).await;
                             }
                         }
                         TuiCommand::SaveNode { node: target_node } => {
@@ -359,68 +470,174 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         TuiCommand::RemoveNode { hash } => {
                             log::info!("Removing node: {}", hex::encode(hash));
                             network_client_clone.registry_mut().await.remove(&hash);
+                            bookmarks.remove_node(&hash);
                         }
                         TuiCommand::ToggleNodeIdentify { hash } => {
                             log::info!("Toggling self-identify for node: {}", hex::encode(hash));
                             network_client_clone.registry_mut().await.toggle_identify(&hash);
                         }
+                        TuiCommand::RecordHistory { entry } => {
+                            history.record(entry);
+                        }
+                        TuiCommand::SaveBookmark { bookmark } => {
+                            log::info!("Saving bookmark: {} ({})", bookmark.title, bookmark.url);
+                            bookmarks.save(bookmark);
+                        }
+                        TuiCommand::SendMessage { message } => {
+                            let peer = message.destination;
+                            let id = message.id();
+                            log::info!("Sending message {} to {}", id, hex::encode(peer));
+                            conversations.record(peer, StoredMessage::outgoing(&message));
+                            let internal_tx = internal_tx.clone();
+                            tokio::spawn(async move {
+                                let result = send_message(&message, message_service_id, &internal_tx).await;
+                                let _ = internal_tx.send(InternalCmd::MessageSent { peer, id, result }).await;
+                            });
+                        }
+                        TuiCommand::MarkConversationRead { peer } => {
+                            conversations.mark_read(peer);
+                        }
+                        TuiCommand::RemoveBookmark { url } => {
+                            log::info!("Removing bookmark: {}", url);
+                            bookmarks.remove(&url);
+                        }
                     }
                 }
                 Some(cmd) = internal_rx.recv() => {
                     match cmd {
                         InternalCmd::Fetch(req) => {
-                            log::info!("Processing fetch request: dest={} path={} identify={} form_data={:?}", hex::encode(req.dest), req.path, req.identify, req.form_data);
-                            let request_data = build_page_request(&req.form_data);
+                            log::info!("Processing fetch request: dest={} path={} identify={} data={} bytes", hex::encode(req.dest), req.path, req.identify, req.data.len());
+                            let request_data = req.data;
+                            let service_id = req.service;
                             let node = node.clone();
                             let path = req.path.clone();
                             let identity_for_req = identity.inner().clone();
 
-                            tokio::spawn(async move {
-                                if node.request_path(req.dest).await.is_err() {
-                                    log::error!("Path request failed");
-                                    let _ = req.reply.send(Err("Path not found".to_string()));
-                                    return;
-                                }
+                            let link_pool = link_pool.clone();
 
-                                let link = match node.establish_link(service_id, req.dest).await {
-                                    Ok(link) => link,
-                                    Err(e) => {
-                                        log::error!("Failed to establish link: {:?}", e);
-                                        let _ = req.reply.send(Err("Failed to establish link".to_string()));
-                                        return;
-                                    }
-                                };
+                            let dest = req.dest;
+                            let identify = req.identify;
+                            let mut reply = req.reply;
+                            let cancel = req.cancel;
+                            let fetches = fetches.clone();
 
-                                if req.identify {
-                                    log::info!("Self-identify enabled, identifying before request");
-                                    node.self_identify(link, &identity_for_req);
-                                }
+                            tokio::spawn(async move {
+                                fetches.fetch_add(1, Ordering::Relaxed);
+                                let result = {
+                                    // Cancelled through the token, or by the requester
+                                    // dropping its receiver. Only this request stops;
+                                    // others sharing the link carry on.
+                                    let cancelled = async {
+                                        tokio::select! {
+                                            _ = reply.closed() => {}
+                                            _ = async {
+                                                match &cancel {
+                                                    Some(cancel) => cancel.cancelled().await,
+                                                    None => std::future::pending().await,
+                                                }
+                                            } => {}
+                                        }
+                                    };
+                                    tokio::pin!(cancelled);
+                                    let mut retried = false;
+                                    loop {
+                                        let checkout = checkout_link(&node, &link_pool, service_id, dest, identify);
+                                        let (link, needs_identify) = tokio::select! {
+                                            checked_out = checkout => match checked_out {
+                                                Ok(checked_out) => checked_out,
+                                                Err(e) => break Err(e),
+                                            },
+                                            _ = &mut cancelled => break Err("Request cancelled".to_string()),
+                                        };
 
-                                log::info!("Calling node.request()");
-                                let result = match node.request(service_id, link, &path, &request_data).await {
-                                    Ok(response) => {
-                                        log::info!("Got response: {} bytes", response.data.len());
-                                        Ok(response.data)
-                                    }
-                                    Err(e) => {
-                                        log::error!("Request failed: {:?}", e);
-                                        let msg = match e {
-                                            RequestError::Timeout => "Request timed out".to_string(),
-                                            RequestError::LinkFailed => "Failed to establish link".to_string(),
-                                            RequestError::LinkClosed => "Link closed".to_string(),
-                                            RequestError::TransferFailed => "Transfer failed".to_string(),
+                                        if needs_identify {
+                                            log::info!("Self-identify enabled, identifying before request");
+                                            node.self_identify(link, &identity_for_req);
+                                            link_pool.lock().await.mark_identified(&dest);
+                                        }
+
+                                        log::info!("Calling node.request()");
+                                        let response = tokio::select! {
+                                            response = node.request(service_id, link, &path, &request_data) => response,
+                                            _ = &mut cancelled => {
+                                                log::info!("Fetch of {} cancelled", path);
+                                                link_pool.lock().await.abandon(&dest, link);
+                                                break Err("Request cancelled".to_string());
+                                            }
                                         };
-                                        Err(msg)
+                                        let mut pool = link_pool.lock().await;
+                                        pool.release(link);
+                                        match response {
+                                            Ok(response) => {
+                                                log::info!("Got response: {} bytes", response.data.len());
+                                                break Ok(response.data);
+                                            }
+                                            Err(e) => {
+                                                log::error!("Request failed: {:?}", e);
+                                                // Only a dead link is dropped; a slow or failed
+                                                // request says nothing about the others on it.
+                                                if matches!(e, RequestError::LinkClosed | RequestError::LinkFailed) {
+                                                    pool.evict(&dest, link);
+                                                }
+                                                drop(pool);
+                                                if matches!(e, RequestError::LinkClosed) && !retried {
+                                                    log::info!("Link closed, retrying on a fresh link");
+                                                    retried = true;
+                                                    continue;
+                                                }
+                                                let msg = match e {
+                                                    RequestError::Timeout => "Request timed out".to_string(),
+                                                    RequestError::LinkFailed => "Failed to establish link".to_string(),
+                                                    RequestError::LinkClosed => "Link closed".to_string(),
+                                                    RequestError::TransferFailed => "Transfer failed".to_string(),
+                                                };
+                                                break Err(msg);
+                                            }
+                                        }
                                     }
                                 };
+                                for link in link_pool.lock().await.take_stale() {
+                                    node.close_link(link);
+                                }
+                                fetches.fetch_sub(1, Ordering::Relaxed);
                                 log::info!("Sending reply");
-                                let _ = req.reply.send(result);
+                                let _ = reply.send(result);
                             });
                         }
                         InternalCmd::GetStats(reply) => {
                             let stats = node.stats().await;
                             let _ = reply.send(stats);
                         }
+                        InternalCmd::MessageReceived(message) => {
+                            let peer = message.source;
+                            let stored = StoredMessage::incoming(&message);
+                            if conversations.record(peer, stored.clone()) {
+                                log::info!("Message {} from {}", stored.id, hex::encode(peer));
+                                let _ = event_tx_clone.send(NetworkEvent::MessageReceived { peer, message: stored }).await;
+                            }
+                        }
+                        InternalCmd::MessageSent { peer, id, result } => {
+                            let event = match result {
+                                Ok(state) => {
+                                    conversations.set_state(peer, &id, state);
+                                    NetworkEvent::MessageSent { peer, id, state }
+                                }
+                                Err(reason) => {
+                                    log::warn!("Message {} failed: {}", id, reason);
+                                    conversations.set_state(peer, &id, MessageState::Failed);
+                                    NetworkEvent::MessageFailed { peer, id, reason }
+                                }
+                            };
+                            let _ = event_tx_clone.send(event).await;
+                        }
+                        InternalCmd::DownloadFinished(transfer_id) => {
+                            downloading.remove(&transfer_id);
+                            let mut queue = downloads.lock().await;
+                            queue.finish(transfer_id);
+                            if let Some(job) = queue.start_next() {
+                                downloading.insert(job.transfer_id, spawn_download(job, service_id, downloads.clone(), event_tx_clone.clone(), internal_tx.clone()));
+                            }
+                        }
                     }
                 }
             }
@@ -429,15 +646,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let network_client_for_receive = network_client.clone();
     let event_tx_receive = event_tx.clone();
-    let node_for_destinations = node.clone();
-    let destinations_task = tokio::spawn(async move {
-        let mut node = (*node_for_destinations).clone();
-        loop {
-            node.destinations_changed().await;
-            let destinations = node.known_destinations().await;
-            network_client_for_receive
-                .handle_destinations_changed(destinations)
-                .await;
+    let node_for_announces = node.clone();
+    let announces_task = tokio::spawn(async move {
+        while let Some(event) = node_for_announces.recv_announce().await {
+            network_client_for_receive.handle_announce(event).await;
         }
     });
 
@@ -445,24 +657,121 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let progress_task = tokio::spawn(async move {
         loop {
             if let Some(progress) = node_for_progress.recv_progress(service_id).await {
-                let _ = event_tx_receive
-                    .send(NetworkEvent::ResourceProgress {
+                // rinse doesn't say which request a resource belongs to.
+                if fetches_for_progress.load(Ordering::Relaxed) != 1 {
+                    continue;
+                }
+                let active = downloads_for_progress
+                    .lock()
+                    .await
+                    .progress(progress.received_bytes, progress.total_bytes);
+                let event = match active {
+                    Some((transfer_id, received_bytes, total_bytes)) => {
+                        NetworkEvent::DownloadProgress {
+                            transfer_id,
+                            received_bytes: received_bytes as usize,
+                            total_bytes: total_bytes as usize,
+                        }
+                    }
+                    None => NetworkEvent::ResourceProgress {
                         received_bytes: progress.received_bytes,
                         total_bytes: progress.total_bytes,
-                    })
-                    .await;
+                    },
+                };
+                let _ = event_tx_receive.send(event).await;
             }
         }
     });
 
-    let mut node_announces = network_client.node_announces();
+    let node_for_links = node.clone();
+    let link_sweep = std::time::Duration::from_secs(settings.links.idle_timeout_secs.max(1));
+    let links_task = tokio::spawn(async move {
+        let mut sweep = tokio::time::interval(link_sweep);
+        loop {
+            sweep.tick().await;
+            let stale = {
+                let mut pool = link_pool_for_sweep.lock().await;
+                pool.prune_idle();
+                pool.take_stale()
+            };
+            for link in stale {
+                node_for_links.close_link(link);
+            }
+        }
+    });
+
+    let node_for_host = node.clone();
+    let page_host = Arc::new(PageHost::new(&settings.hosting));
+    let host_task = tokio::spawn(async move {
+        loop {
+            if let Some(req) = node_for_host.recv_request(service_id).await {
+                log::info!("Incoming request for {}", req.path);
+                let node = node_for_host.clone();
+                let page_host = page_host.clone();
+                tokio::spawn(async move {
+                    let response = page_host
+                        .handle(&req.path, &req.data, req.remote_identity)
+                        .await;
+                    node.respond(req.link, req.request_id, &response);
+                });
+            }
+        }
+    });
+
+    let node_for_messages = node.clone();
+    let messages_task = tokio::spawn(async move {
+        loop {
+            if let Some(req) = node_for_messages.recv_request(message_service_id).await {
+                if req.path != messages::DELIVERY_PATH {
+                    log::info!("Ignoring message request for {}", req.path);
+                    continue;
+                }
+                let sender = req
+                    .remote_identity
+                    .map(|id| messages::delivery_address(&id));
+                match DirectMessage::unpack(&req.data) {
+                    Ok(message) if message.destination != message_address => {
+                        log::warn!("Dropping message for {}", hex::encode(message.destination))
+                    }
+                    // Messages aren't signed, so the link's identity stands in
+                    // for the signature: the sender must have identified as
+                    // the identity behind the source it claims.
+                    Ok(message) if sender != Some(message.source) => log::warn!(
+                        "Dropping message claiming to be from {}: link identified as {}",
+                        hex::encode(message.source),
+                        req.remote_identity
+                            .map_or("nobody".to_string(), hex::encode)
+                    ),
+                    Ok(message) => {
+                        // The reply doubles as the delivery receipt.
+                        node_for_messages.respond(
+                            req.link,
+                            req.request_id,
+                            message.id().as_bytes(),
+                        );
+                        let _ = internal_tx_messages
+                            .send(InternalCmd::MessageReceived(message))
+                            .await;
+                    }
+                    Err(e) => log::warn!("Dropping malformed message: {}", e),
+                }
+            }
+        }
+    });
+
+    let mut announces = network_client.announces();
     let event_tx_announce = event_tx.clone();
 
     let announce_task = tokio::spawn(async move {
-        while let Ok(node) = node_announces.recv().await {
-            log::info!("Node announce: {} ({})", node.name, node.hash_hex());
+        while let Ok(announce) = announces.recv().await {
+            log::info!(
+                "{} announce: {} ({})",
+                announce.kind.badge(),
+                announce.display_name(),
+                announce.hash_hex()
+            );
             let _ = event_tx_announce
-                .send(NetworkEvent::NodeAnnounce(node))
+                .send(NetworkEvent::Announce(announce))
                 .await;
         }
     });
@@ -494,6 +803,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             event_rx,
             cmd_tx,
         )?;
+        tui.set_history(initial_history);
+        tui.set_discovered(initial_discovered);
+        tui.set_bookmarks(initial_bookmarks);
+        tui.set_conversations(message_address, initial_conversations);
         tui.run()
     })
     .await?;
@@ -502,14 +815,511 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _ = network_task.await;
     announce_task.abort();
     stats_task.abort();
-    destinations_task.abort();
+    announces_task.abort();
     progress_task.abort();
+    links_task.abort();
+    host_task.abort();
+    messages_task.abort();
     node_task.abort();
 
     tui_result?;
     Ok(())
 }
 
+/// Fetches one queued download and writes it into its target directory,
+/// telling the network task when it is done so the next one can start.
+fn spawn_download(
+    job: DownloadJob,
+    service: ServiceId,
+    downloads: Arc<Mutex<DownloadQueue>>,
+    event_tx: mpsc::Sender<NetworkEvent>,
+    internal_tx: mpsc::Sender<InternalCmd>,
+) -> DownloadTask {
+    let task = DownloadTask::default();
+    let cancel = task.cancel.clone();
+    let discard = task.discard.clone();
+    tokio::spawn(async move {
+        let transfer_id = job.transfer_id;
+        let part = job.part_path();
+        let resumed_from = tokio::fs::metadata(&part)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let _ = event_tx
+            .send(NetworkEvent::DownloadStarted {
+                transfer_id,
+                resumed_from,
+            })
+            .await;
+
+        let result = match download_to_part(
+            &job,
+            service,
+            &part,
+            &downloads,
+            &event_tx,
+            &internal_tx,
+            &cancel,
+        )
+        .await
+        {
+            // Resolved only now so downloads queued with the same name
+            // don't collide, and an existing file is never overwritten.
+            Ok(()) => {
+                let file_path = unique_path(&job.dir, &job.filename);
+                tokio::fs::rename(&part, &file_path)
+                    .await
+                    .map_err(|e| format!("Failed to save file: {}", e))
+                    .map(|_| file_path.display().to_string())
+            }
+            Err(e) => Err(e),
+        };
+
+        let event = match result {
+            Ok(path) => {
+                log::info!("Download complete: {}", path);
+                Some(NetworkEvent::DownloadComplete { transfer_id, path })
+            }
+            Err(_) if cancel.is_cancelled() => {
+                if discard.load(Ordering::Relaxed) {
+                    remove_part(&part).await;
+                }
+                None
+            }
+            Err(reason) => {
+                log::error!("Download failed: {}", reason);
+                Some(NetworkEvent::DownloadFailed {
+                    transfer_id,
+                    reason,
+                })
+            }
+        };
+        if let Some(event) = event {
+            let _ = event_tx.send(event).await;
+        }
+        let _ = internal_tx
+            .send(InternalCmd::DownloadFinished(transfer_id))
+            .await;
+    });
+    task
+}
+
+/// Stops a running download, closing the link it was using.
+#[derive(Default)]
+struct DownloadTask {
+    cancel: CancellationToken,
+    /// Delete what has been downloaded so far instead of keeping it to resume.
+    discard: Arc<AtomicBool>,
+}
+
+impl DownloadTask {
+    fn cancel(&self, discard: bool) {
+        self.discard.store(discard, Ordering::Relaxed);
+        self.cancel.cancel();
+    }
+}
+
+async fn remove_part(part: &Path) {
+    match tokio::fs::remove_file(part).await {
+        Ok(()) => log::info!("Removed {}", part.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => log::warn!("Failed to remove {}: {}", part.display(), e),
+    }
+}
+
+/// Fetches a file chunk by chunk, appending each to `part` as it arrives so
+/// an interrupted download keeps what it has.
+///
+/// Chunking relies on the `nomad_range` request field, which only nomad hosts
+/// understand. Stock NomadNet nodes answer with the whole file as a single
+/// resource. rinse hands that over only once complete; its progress reports
+/// carry byte counts, not data. So such a file is held in memory and written
+/// in one go, and an interrupted transfer starts over.
+async fn download_to_part(
+    job: &DownloadJob,
+    service: ServiceId,
+    part: &Path,
+    downloads: &Mutex<DownloadQueue>,
+    event_tx: &mpsc::Sender<NetworkEvent>,
+    internal_tx: &mpsc::Sender<InternalCmd>,
+    cancel: &CancellationToken,
+) -> Result<(), String> {
+    tokio::fs::create_dir_all(&job.dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", job.dir.display(), e))?;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(part)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", part.display(), e))?;
+    let write_err = |e: std::io::Error| format!("Failed to write file: {}", e);
+
+    let mut offset = file.metadata().await.map_err(write_err)?.len();
+    let mut restarted = false;
+    loop {
+        if cancel.is_cancelled() {
+            return Err("Download cancelled".to_string());
+        }
+        let mut queue = downloads.lock().await;
+        queue.set_offset(job.transfer_id, offset, None);
+        queue.set_fetching(job.transfer_id, true);
+        drop(queue);
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let _ = internal_tx
+            .send(InternalCmd::Fetch(FetchReq {
+                service,
+                dest: job.node.hash,
+                path: job.path.clone(),
+                data: build_page_request(&range_request(offset, CHUNK_SIZE)),
+                identify: job.node.identify,
+                reply: reply_tx,
+                cancel: Some(cancel.clone()),
+            }))
+            .await;
+        let reply = reply_rx.await;
+        downloads.lock().await.set_fetching(job.transfer_id, false);
+        let data = reply.map_err(|_| "Request cancelled".to_string())??;
+
+        let Some(chunk) = FileChunk::decode(&data) else {
+            // Not a nomad host: the whole file arrived as one buffered resource.
+            log::info!("Host sent the whole file ({} bytes)", data.len());
+            file.set_len(0).await.map_err(write_err)?;
+            file.write_all(&data).await.map_err(write_err)?;
+            return file.flush().await.map_err(write_err);
+        };
+        if chunk.offset != offset || offset > chunk.total {
+            // The part on disk doesn't fit the file the host has now, e.g. it
+            // changed since. Start over rather than splice the two.
+            if restarted || offset == 0 {
+                return Err(format!(
+                    "Host sent data at {} instead of {}",
+                    chunk.offset, offset
+                ));
+            }
+            log::warn!(
+                "Part file doesn't match the host's file ({} of {} bytes, host sent {}), restarting",
+                offset,
+                chunk.total,
+                chunk.offset
+            );
+            file.set_len(0).await.map_err(write_err)?;
+            offset = 0;
+            restarted = true;
+            continue;
+        }
+
+        file.write_all(&chunk.data).await.map_err(write_err)?;
+        file.flush().await.map_err(write_err)?;
+        offset += chunk.data.len() as u64;
+
+        let _ = event_tx
+            .send(NetworkEvent::DownloadProgress {
+                transfer_id: job.transfer_id,
+                received_bytes: offset as usize,
+                total_bytes: chunk.total as usize,
+            })
+            .await;
+        downloads
+            .lock()
+            .await
+            .set_offset(job.transfer_id, offset, Some(chunk.total));
+
+        if offset >= chunk.total || chunk.data.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+/// Starts an enabled interface, returning its task unless a server
+/// couldn't bind.
+async fn start_interface(
+    name: &str,
+    config: &InterfaceConfig,
+    node: &rinse::Node<TcpTransport>,
+    services: [ServiceId; 2],
+    traffic: Arc<Traffic>,
+    event_tx: &mpsc::Sender<NetworkEvent>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    match config {
+        InterfaceConfig::TCPClientInterface {
+            target_host,
+            target_port,
+            ..
+        } => {
+            let addr = config_file::interface_address(target_host, *target_port);
+            Some(spawn_tcp_client(
+                name.to_string(),
+                addr,
+                node.clone(),
+                services,
+                traffic,
+                event_tx.clone(),
+            ))
+        }
+        InterfaceConfig::TCPServerInterface {
+            listen_ip,
+            listen_port,
+            ..
+        } => {
+            let addr = config_file::interface_address(listen_ip, *listen_port);
+            let (server, status) = match tokio::net::TcpListener::bind(&addr).await {
+                Ok(listener) => {
+                    log::info!("Listening on {} ({})", name, addr);
+                    let server = spawn_tcp_server(
+                        name.to_string(),
+                        listener,
+                        node.clone(),
+                        traffic,
+                        event_tx.clone(),
+                    );
+                    (Some(server), format!("Listening on {}", addr))
+                }
+                Err(e) => {
+                    log::warn!("Failed to listen on {}: {}", addr, e);
+                    (None, format!("Failed to listen on {}: {}", addr, e))
+                }
+            };
+            let _ = event_tx
+                .send(NetworkEvent::InterfaceStatus {
+                    name: name.to_string(),
+                    connected: server.is_some(),
+                    retry: None,
+                })
+                .await;
+            let _ = event_tx.send(NetworkEvent::Status(status)).await;
+            server
+        }
+    }
+}
+
+/// Checks out a pooled link to `dest`, opening one if there is none.
+/// Concurrent misses share the first request's handshake.
+async fn checkout_link(
+    node: &rinse::Node<TcpTransport>,
+    link_pool: &Mutex<LinkPool<rinse::LinkId>>,
+    service: ServiceId,
+    dest: [u8; 16],
+    identify: bool,
+) -> Result<(rinse::LinkId, bool), String> {
+    if let Some(pooled) = link_pool.lock().await.get(&dest, identify) {
+        log::info!("Reusing link to {}", hex::encode(dest));
+        return Ok(pooled);
+    }
+
+    let gate = link_pool.lock().await.connect_gate(dest);
+    let _connecting = gate.lock().await;
+    if let Some(pooled) = link_pool.lock().await.get(&dest, identify) {
+        log::info!("Reusing link to {} opened meanwhile", hex::encode(dest));
+        return Ok(pooled);
+    }
+
+    if node.request_path(dest).await.is_err() {
+        log::error!("Path request failed");
+        return Err("Path not found".to_string());
+    }
+    match node.establish_link(service, dest).await {
+        Ok(link) => {
+            link_pool.lock().await.insert(dest, link);
+            Ok((link, identify))
+        }
+        Err(e) => {
+            log::error!("Failed to establish link: {:?}", e);
+            Err("Failed to establish link".to_string())
+        }
+    }
+}
+
+/// Stops an interface's task and waits for it, so its sockets are closed
+/// and a server's address is free again.
+async fn stop_interface(running: &mut HashMap<String, tokio::task::JoinHandle<()>>, name: &str) {
+    if let Some(task) = running.remove(name) {
+        task.abort();
+        let _ = task.await;
+    }
+}
+
+/// Re-reads config.toml after an edit and sends the TUI the new list.
+async fn reload_interfaces(
+    event_tx: &mpsc::Sender<NetworkEvent>,
+) -> Option<HashMap<String, InterfaceConfig>> {
+    match Config::load() {
+        Ok(config) => {
+            let _ = event_tx
+                .send(NetworkEvent::Interfaces(build_interface_info(
+                    &config,
+                    &HashMap::new(),
+                )))
+                .await;
+            Some(enabled_interface_configs(&config))
+        }
+        Err(e) => {
+            log::warn!("Failed to reload config: {}", e);
+            let _ = event_tx
+                .send(NetworkEvent::Status(format!(
+                    "Couldn't reload config.toml: {}",
+                    e
+                )))
+                .await;
+            None
+        }
+    }
+}
+
+/// How often visits recorded in memory are written to the history file.
+const HISTORY_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How often each interface reports its counters to the TUI.
+const TRAFFIC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Keeps a TCP client interface connected, announcing each time the link
+/// comes up and reporting its state to the TUI.
+fn spawn_tcp_client(
+    name: String,
+    addr: String,
+    node: rinse::Node<TcpTransport>,
+    services: [ServiceId; 2],
+    traffic: Arc<Traffic>,
+    event_tx: mpsc::Sender<NetworkEvent>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let (state_tx, mut state_rx) = tokio::sync::watch::channel(LinkState::Connecting);
+        let connect = {
+            let node = node.clone();
+            let addr = addr.clone();
+            let traffic = traffic.clone();
+            move || {
+                let node = node.clone();
+                let addr = addr.clone();
+                let traffic = traffic.clone();
+                async move {
+                    log::info!("Connecting to {}", addr);
+                    let stream = tokio::net::TcpStream::connect(&addr).await?;
+                    bridge::attach(&node, stream, traffic).await
+                }
+            }
+        };
+        let supervisor = supervisor::supervise(connect, Backoff::default(), state_tx);
+        tokio::pin!(supervisor);
+        let mut report = tokio::time::interval(TRAFFIC_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = &mut supervisor => break,
+                _ = report.tick() => send_traffic(&name, &traffic, &event_tx).await,
+                Ok(()) = state_rx.changed() => {
+                    let (connected, retry) = match *state_rx.borrow_and_update() {
+                        LinkState::Connecting => continue,
+                        LinkState::Connected => (true, None),
+                        LinkState::Retrying(retry) => (false, Some(retry)),
+                    };
+                    if connected {
+                        log::info!("Connected to {} ({})", name, addr);
+                        traffic.link_up();
+                        services.iter().for_each(|service| node.announce(*service));
+                    } else {
+                        traffic.link_down();
+                    }
+                    let _ = event_tx
+                        .send(NetworkEvent::InterfaceStatus {
+                            name: name.clone(),
+                            connected,
+                            retry,
+                        })
+                        .await;
+                }
+            }
+        }
+    })
+}
+
+/// Runs a TCP server interface, reporting its connected peers to the TUI.
+fn spawn_tcp_server(
+    name: String,
+    listener: tokio::net::TcpListener,
+    node: rinse::Node<TcpTransport>,
+    traffic: Arc<Traffic>,
+    event_tx: mpsc::Sender<NetworkEvent>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let (clients_tx, mut clients_rx) = tokio::sync::watch::channel(Vec::new());
+        traffic.link_up();
+        let server = tcp_server::serve(listener, node, clients_tx, traffic.clone());
+        tokio::pin!(server);
+        let mut report = tokio::time::interval(TRAFFIC_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = &mut server => break,
+                _ = report.tick() => send_traffic(&name, &traffic, &event_tx).await,
+                Ok(()) = clients_rx.changed() => {
+                    let clients = clients_rx
+                        .borrow_and_update()
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect();
+                    let _ = event_tx
+                        .send(NetworkEvent::InterfaceClients {
+                            name: name.clone(),
+                            clients,
+                        })
+                        .await;
+                }
+            }
+        }
+    })
+}
+
+async fn send_traffic(name: &str, traffic: &Traffic, event_tx: &mpsc::Sender<NetworkEvent>) {
+    let _ = event_tx
+        .send(NetworkEvent::InterfaceTraffic {
+            name: name.to_string(),
+            stats: traffic.snapshot(),
+        })
+        .await;
+}
+
+/// Sends `request` to a message destination over an identified link.
+async fn message_request(
+    internal_tx: &mpsc::Sender<InternalCmd>,
+    service: ServiceId,
+    dest: [u8; 16],
+    path: &str,
+    data: Vec<u8>,
+) -> Result<Vec<u8>, String> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    let _ = internal_tx
+        .send(InternalCmd::Fetch(FetchReq {
+            service,
+            dest,
+            path: path.to_string(),
+            data,
+            identify: true,
+            reply: reply_tx,
+            cancel: None,
+        }))
+        .await;
+    reply_rx
+        .await
+        .map_err(|_| "Request cancelled".to_string())?
+}
+
+/// Delivers a message directly to the peer; the reply is its receipt.
+async fn send_message(
+    message: &DirectMessage,
+    service: ServiceId,
+    internal_tx: &mpsc::Sender<InternalCmd>,
+) -> Result<MessageState, String> {
+    message_request(
+        internal_tx,
+        service,
+        message.destination,
+        messages::DELIVERY_PATH,
+        message.pack(),
+    )
+    .await
+    .map(|_| MessageState::Delivered)
+}
+
 fn build_page_request(form_data: &HashMap<String, String>) -> Vec<u8> {
     if form_data.is_empty() {
         Vec::new()
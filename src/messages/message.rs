@@ -0,0 +1,208 @@
+use serde::de::{self, IgnoredAny, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_bytes::ByteBuf;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+const HASH_LEN: usize = 16;
+const HEADER_LEN: usize = 2 * HASH_LEN;
+const NAME_HASH_LEN: usize = 10;
+
+#[derive(Error, Debug)]
+pub enum MessageError {
+    #[error("message is {0} bytes, too short for a message header")]
+    TooShort(usize),
+    #[error("invalid payload: {0}")]
+    Payload(#[from] rmp_serde::decode::Error),
+}
+
+/// A direct message between nomad clients: destination, source, then a
+/// msgpack `[timestamp, title, content, fields]` payload.
+///
+/// This is not LXMF. rinse doesn't expose identity signing, so messages
+/// carry no signature and the sender is authenticated by the link instead:
+/// see [`delivery_address`]. LXMF clients can't read them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectMessage {
+    pub destination: [u8; 16],
+    pub source: [u8; 16],
+    pub timestamp: f64,
+    pub title: String,
+    pub content: String,
+    id: [u8; 32],
+}
+
+impl DirectMessage {
+    pub fn new(destination: [u8; 16], source: [u8; 16], title: &str, content: &str) -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+        let mut message = Self {
+            destination,
+            source,
+            timestamp,
+            title: title.to_string(),
+            content: content.to_string(),
+            id: [0; 32],
+        };
+        message.id = message_id(&destination, &source, &message.payload());
+        message
+    }
+
+    /// The message hash, hex encoded. Used to match delivery results
+    /// and to drop duplicates.
+    pub fn id(&self) -> String {
+        hex::encode(self.id)
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let payload = self.payload();
+        let mut packed = Vec::with_capacity(HEADER_LEN + payload.len());
+        packed.extend_from_slice(&self.destination);
+        packed.extend_from_slice(&self.source);
+        packed.extend_from_slice(&payload);
+        packed
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, MessageError> {
+        if data.len() < HEADER_LEN {
+            return Err(MessageError::TooShort(data.len()));
+        }
+        let mut destination = [0; 16];
+        let mut source = [0; 16];
+        destination.copy_from_slice(&data[..HASH_LEN]);
+        source.copy_from_slice(&data[HASH_LEN..2 * HASH_LEN]);
+        let payload = &data[HEADER_LEN..];
+        let Payload {
+            timestamp,
+            title,
+            content,
+        } = rmp_serde::from_slice(payload)?;
+
+        Ok(Self {
+            destination,
+            source,
+            timestamp,
+            title: String::from_utf8_lossy(&title).into_owned(),
+            content: String::from_utf8_lossy(&content).into_owned(),
+            id: message_id(&destination, &source, payload),
+        })
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let payload = (
+            self.timestamp,
+            ByteBuf::from(self.title.as_bytes().to_vec()),
+            ByteBuf::from(self.content.as_bytes().to_vec()),
+            HashMap::<u8, ByteBuf>::new(),
+        );
+        rmp_serde::to_vec(&payload).unwrap_or_default()
+    }
+}
+
+/// The `nomad.messages` destination of an identity, derived the way
+/// Reticulum derives every destination hash. A message is only accepted
+/// when this matches its source for the identity its link identified as.
+pub fn delivery_address(identity: &[u8; 16]) -> [u8; 16] {
+    let name_hash = Sha256::digest(format!("{}.{}", super::APP_NAME, super::DELIVERY_ASPECT));
+    let mut hasher = Sha256::new();
+    hasher.update(&name_hash[..NAME_HASH_LEN]);
+    hasher.update(identity);
+    let mut address = [0; HASH_LEN];
+    address.copy_from_slice(&hasher.finalize()[..HASH_LEN]);
+    address
+}
+
+fn message_id(destination: &[u8; 16], source: &[u8; 16], payload: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(destination);
+    hasher.update(source);
+    hasher.update(payload);
+    hasher.finalize().into()
+}
+
+/// The parts of a payload we show. Fields and anything a newer client
+/// appends after them are skipped.
+struct Payload {
+    timestamp: f64,
+    title: ByteBuf,
+    content: ByteBuf,
+}
+
+impl<'de> Deserialize<'de> for Payload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PayloadVisitor;
+
+        impl<'de> Visitor<'de> for PayloadVisitor {
+            type Value = Payload;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a message payload array")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Payload, A::Error> {
+                let timestamp = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let title = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let content = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(Payload {
+                    timestamp,
+                    title,
+                    content,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(PayloadVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_round_trip() {
+        let message = DirectMessage::new([1; 16], [2; 16], "", "hello over the mesh");
+        let packed = message.pack();
+        assert_eq!(&packed[..16], &[1; 16]);
+        assert_eq!(&packed[16..32], &[2; 16]);
+
+        let unpacked = DirectMessage::unpack(&packed).unwrap();
+        assert_eq!(unpacked, message);
+        assert_eq!(unpacked.id(), message.id());
+        assert!(matches!(
+            DirectMessage::unpack(&packed[..20]),
+            Err(MessageError::TooShort(20))
+        ));
+    }
+
+    #[test]
+    fn test_unpack_tolerates_string_fields_and_extra_elements() {
+        let payload = rmp_serde::to_vec(&(
+            1_700_000_000.0f64,
+            "Subject",
+            "Body text",
+            HashMap::from([(1u8, "attachment")]),
+            ByteBuf::from(vec![0xaa; 32]),
+        ))
+        .unwrap();
+        let mut data = vec![3; 16];
+        data.extend([4; 16]);
+        data.extend(&payload);
+
+        let message = DirectMessage::unpack(&data).unwrap();
+        assert_eq!(message.title, "Subject");
+        assert_eq!(message.content, "Body text");
+        assert_eq!(message.source, [4; 16]);
+    }
+}
@@ -0,0 +1,15 @@
+mod message;
+mod store;
+
+pub use message::{delivery_address, DirectMessage};
+pub use store::{Conversation, ConversationStore, MessageState, StoredMessage, CONVERSATIONS_DIR};
+
+/// App name and aspect of the destination direct messages are delivered to.
+/// This is nomad's own protocol, not LXMF, so it stays out of `lxmf.delivery`
+/// where LXMF clients would try to deliver to it.
+pub const APP_NAME: &str = "nomad";
+pub const DELIVERY_ASPECT: &str = "messages";
+
+/// Request path messages are handed over on once a link to the peer's
+/// delivery destination is up.
+pub const DELIVERY_PATH: &str = "/nomad/messages";
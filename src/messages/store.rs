@@ -0,0 +1,219 @@
+use super::DirectMessage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CONVERSATIONS_DIR: &str = ".rinse/conversations";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageState {
+    Sending,
+    Delivered,
+    Failed,
+    Received,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredMessage {
+    pub id: String,
+    pub timestamp: f64,
+    pub outgoing: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    pub content: String,
+    pub state: MessageState,
+}
+
+impl StoredMessage {
+    pub fn outgoing(message: &DirectMessage) -> Self {
+        Self::from_message(message, true, MessageState::Sending)
+    }
+
+    pub fn incoming(message: &DirectMessage) -> Self {
+        Self::from_message(message, false, MessageState::Received)
+    }
+
+    fn from_message(message: &DirectMessage, outgoing: bool, state: MessageState) -> Self {
+        Self {
+            id: message.id(),
+            timestamp: message.timestamp,
+            outgoing,
+            title: message.title.clone(),
+            content: message.content.clone(),
+            state,
+        }
+    }
+}
+
+/// Every message exchanged with one peer, keyed by the peer's
+/// `nomad.messages` hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conversation {
+    pub peer: String,
+    #[serde(default)]
+    pub unread: usize,
+    #[serde(default)]
+    pub messages: Vec<StoredMessage>,
+}
+
+impl Conversation {
+    pub fn new(peer: [u8; 16]) -> Self {
+        Self {
+            peer: hex::encode(peer),
+            unread: 0,
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn peer_hash(&self) -> Option<[u8; 16]> {
+        hex::decode(&self.peer).ok()?.try_into().ok()
+    }
+
+    pub fn last_activity(&self) -> f64 {
+        self.messages.last().map(|m| m.timestamp).unwrap_or(0.0)
+    }
+
+    /// Adds a message in timestamp order. Returns false if it was already
+    /// there, as happens when a sender retries after a lost reply.
+    pub fn push(&mut self, message: StoredMessage) -> bool {
+        if self.messages.iter().any(|m| m.id == message.id) {
+            return false;
+        }
+        let pos = self
+            .messages
+            .partition_point(|m| m.timestamp <= message.timestamp);
+        self.messages.insert(pos, message);
+        true
+    }
+
+    pub fn set_state(&mut self, id: &str, state: MessageState) -> bool {
+        match self.messages.iter_mut().find(|m| m.id == id) {
+            Some(message) => {
+                message.state = state;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Conversations kept one file per peer, so a busy thread doesn't rewrite
+/// everything else on each message.
+pub struct ConversationStore {
+    dir: PathBuf,
+    conversations: Vec<Conversation>,
+}
+
+impl ConversationStore {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        let conversations = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+            .filter_map(|contents| toml::from_str::<Conversation>(&contents).ok())
+            .collect();
+        Self { dir, conversations }
+    }
+
+    pub fn all(&self) -> &[Conversation] {
+        &self.conversations
+    }
+
+    /// Records a message with `peer`, counting it as unread if it came in.
+    /// Returns false for a duplicate.
+    pub fn record(&mut self, peer: [u8; 16], message: StoredMessage) -> bool {
+        let incoming = !message.outgoing;
+        let conversation = self.get_or_create(peer);
+        if !conversation.push(message) {
+            return false;
+        }
+        if incoming {
+            conversation.unread += 1;
+        }
+        self.persist(peer);
+        true
+    }
+
+    pub fn set_state(&mut self, peer: [u8; 16], id: &str, state: MessageState) {
+        let changed = self
+            .get_mut(peer)
+            .is_some_and(|conversation| conversation.set_state(id, state));
+        if changed {
+            self.persist(peer);
+        }
+    }
+
+    pub fn mark_read(&mut self, peer: [u8; 16]) {
+        if let Some(conversation) = self.get_mut(peer).filter(|c| c.unread > 0) {
+            conversation.unread = 0;
+            self.persist(peer);
+        }
+    }
+
+    fn get_mut(&mut self, peer: [u8; 16]) -> Option<&mut Conversation> {
+        let key = hex::encode(peer);
+        self.conversations.iter_mut().find(|c| c.peer == key)
+    }
+
+    fn get_or_create(&mut self, peer: [u8; 16]) -> &mut Conversation {
+        let key = hex::encode(peer);
+        let pos = match self.conversations.iter().position(|c| c.peer == key) {
+            Some(pos) => pos,
+            None => {
+                self.conversations.push(Conversation::new(peer));
+                self.conversations.len() - 1
+            }
+        };
+        &mut self.conversations[pos]
+    }
+
+    fn persist(&self, peer: [u8; 16]) {
+        let key = hex::encode(peer);
+        let Some(conversation) = self.conversations.iter().find(|c| c.peer == key) else {
+            return;
+        };
+        if let Err(e) = fs::create_dir_all(&self.dir) {
+            log::warn!("Failed to create {}: {}", self.dir.display(), e);
+            return;
+        }
+        if let Ok(contents) = toml::to_string_pretty(conversation) {
+            let _ = fs::write(self.dir.join(format!("{}.toml", key)), contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_persists_per_peer() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let ours = [1; 16];
+        let peer = [2; 16];
+
+        let mut store = ConversationStore::new(dir);
+        let sent = DirectMessage::new(peer, ours, "", "hi");
+        let reply = DirectMessage::new(ours, peer, "", "hello back");
+        assert!(store.record(peer, StoredMessage::outgoing(&sent)));
+        assert!(store.record(peer, StoredMessage::incoming(&reply)));
+        assert!(!store.record(peer, StoredMessage::incoming(&reply)));
+        store.set_state(peer, &sent.id(), MessageState::Delivered);
+
+        let reloaded = ConversationStore::new(dir);
+        let conversation = &reloaded.all()[0];
+        assert_eq!(conversation.peer_hash(), Some(peer));
+        assert_eq!(conversation.unread, 1);
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[0].state, MessageState::Delivered);
+        assert_eq!(conversation.messages[1].content, "hello back");
+
+        store.mark_read(peer);
+        assert_eq!(ConversationStore::new(dir).all()[0].unread, 0);
+    }
+}
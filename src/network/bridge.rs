@@ -0,0 +1,107 @@
+use crate::network::traffic::{FrameCounter, Traffic};
+use rinse::{Interface, Node, TcpTransport};
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// How long to wait for the node's own connection on the loopback listener.
+const ACCEPT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Hands an already open socket to the node as an interface, returning the
+/// future that carries its traffic, counted into `traffic`.
+///
+/// rinse only opens TCP interfaces by dialing out, so the node dials a
+/// one-off loopback listener and the two sockets are piped together. Anyone
+/// on the host could dial that listener too, so only the connection coming
+/// from the transport's own address is taken.
+pub async fn attach(
+    node: &Node<TcpTransport>,
+    stream: TcpStream,
+    traffic: Arc<Traffic>,
+) -> io::Result<impl Future<Output = io::Result<()>> + Send> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?.to_string();
+    let (accepted, transport) = tokio::join!(listener.accept(), TcpTransport::connect(&addr));
+    let transport = transport?;
+    let dialer = transport.local_addr()?;
+    let (mut local, mut peer) = accepted?;
+    while peer != dialer {
+        log::warn!("Dropping stray connection from {} to the bridge", peer);
+        (local, peer) = tokio::time::timeout(ACCEPT_TIMEOUT, listener.accept())
+            .await
+            .map_err(|_| {
+                io::Error::new(io::ErrorKind::TimedOut, "node never dialed the bridge")
+            })??;
+    }
+    drop(listener);
+
+    node.add_interface(Interface::new(transport));
+    Ok(pipe(stream, local, traffic))
+}
+
+/// Pipes `remote` and `local` together until either side closes. Waiting for
+/// both would leave a half-closed peer looking connected.
+pub async fn pipe(
+    mut remote: TcpStream,
+    mut local: TcpStream,
+    traffic: Arc<Traffic>,
+) -> io::Result<()> {
+    let (remote_rd, remote_wr) = remote.split();
+    let (local_rd, local_wr) = local.split();
+    tokio::select! {
+        result = pump(remote_rd, local_wr, |data, frames| traffic.received(data, frames)) => result,
+        result = pump(local_rd, remote_wr, |data, frames| traffic.sent(data, frames)) => result,
+    }
+}
+
+/// Copies one direction until it closes, reporting each chunk to `count`.
+async fn pump(
+    mut from: impl AsyncRead + Unpin,
+    mut to: impl AsyncWrite + Unpin,
+    count: impl Fn(&[u8], &mut FrameCounter),
+) -> io::Result<()> {
+    let mut frames = FrameCounter::default();
+    let mut buf = vec![0; 16 * 1024];
+    loop {
+        let n = from.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        to.write_all(&buf[..n]).await?;
+        count(&buf[..n], &mut frames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn socket_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (accepted, dialed) = tokio::join!(listener.accept(), TcpStream::connect(addr));
+        (accepted.unwrap().0, dialed.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_ends_when_one_side_closes() {
+        let (remote, mut remote_peer) = socket_pair().await;
+        let (local, mut node_side) = socket_pair().await;
+        let traffic = Arc::new(Traffic::default());
+        let pipe = tokio::spawn(pipe(remote, local, traffic.clone()));
+
+        remote_peer.write_all(&[1, 2, 3]).await.unwrap();
+        let mut buf = [0; 3];
+        node_side.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+
+        // The peer hangs up while the node's side stays open.
+        drop(remote_peer);
+        let result = tokio::time::timeout(Duration::from_secs(5), pipe).await;
+        assert!(matches!(result, Ok(Ok(Ok(())))));
+        assert_eq!(traffic.snapshot().bytes_in, 3);
+    }
+}
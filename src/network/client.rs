@@ -1,75 +1,75 @@
+use crate::network::discovery_store::DiscoveryStore;
 use crate::network::node_registry::NodeRegistry;
-use crate::network::types::NodeInfo;
+use crate::network::types::{Announce, AnnounceKind, Sighting};
 
-use rinse::{Address, AspectHash, Destination};
+use rinse::{AnnounceEvent, AspectHash};
 
-use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 
 const NODE_ASPECT_NAME: &str = "nomadnetwork.node";
+const MESSAGES_ASPECT_NAME: &str = "nomad.messages";
+const PROPAGATION_ASPECT_NAME: &str = "lxmf.propagation";
 
 pub struct NetworkClient {
     registry: Arc<RwLock<NodeRegistry>>,
-    known_addresses: Arc<RwLock<HashSet<Address>>>,
-    node_announce_tx: broadcast::Sender<NodeInfo>,
+    discovered: Arc<RwLock<DiscoveryStore>>,
+    announce_tx: broadcast::Sender<Announce>,
 }
 
 impl NetworkClient {
-    pub fn new(registry: NodeRegistry) -> Self {
-        let (node_announce_tx, _) = broadcast::channel(64);
+    pub fn new(registry: NodeRegistry, discovered: DiscoveryStore) -> Self {
+        let (announce_tx, _) = broadcast::channel(64);
 
         Self {
             registry: Arc::new(RwLock::new(registry)),
-            known_addresses: Arc::new(RwLock::new(HashSet::new())),
-            node_announce_tx,
+            discovered: Arc::new(RwLock::new(discovered)),
+            announce_tx,
         }
     }
 
-    pub fn node_announces(&self) -> broadcast::Receiver<NodeInfo> {
-        self.node_announce_tx.subscribe()
+    /// Discovery entries, updated each time their destination announces.
+    pub fn announces(&self) -> broadcast::Receiver<Announce> {
+        self.announce_tx.subscribe()
     }
 
-    pub async fn handle_destinations_changed(&self, destinations: Vec<Destination>) {
-        let mut known = self.known_addresses.write().await;
-        let node_aspect = AspectHash::from_name(NODE_ASPECT_NAME);
-
-        for dest in destinations {
-            if dest.aspect != node_aspect {
-                continue;
-            }
-
-            let is_new = !known.contains(&dest.address);
-
-            if is_new {
-                let name = match dest
-                    .app_data
-                    .as_ref()
-                    .and_then(|data| parse_display_name(data))
-                {
-                    Some(name) => name,
-                    None => {
-                        known.insert(dest.address);
-                        continue;
-                    }
-                };
-
-                let node = NodeInfo {
-                    hash: dest.address,
-                    name,
-                    identify: false,
-                };
-
-                known.insert(dest.address);
-
-                {
-                    let mut reg = self.registry.write().await;
-                    reg.save(node.clone());
-                }
-
-                let _ = self.node_announce_tx.send(node);
-            }
+    pub async fn handle_announce(&self, event: AnnounceEvent) {
+        let aspects = [
+            (AspectHash::from_name(NODE_ASPECT_NAME), AnnounceKind::Node),
+            (
+                AspectHash::from_name(MESSAGES_ASPECT_NAME),
+                AnnounceKind::Peer,
+            ),
+            (
+                AspectHash::from_name(PROPAGATION_ASPECT_NAME),
+                AnnounceKind::PropagationNode,
+            ),
+        ];
+        let dest = event.destination;
+        let Some(&(_, kind)) = aspects.iter().find(|(aspect, _)| *aspect == dest.aspect) else {
+            return;
+        };
+
+        // Propagation nodes announce their configuration, not a name.
+        let name = match kind {
+            AnnounceKind::PropagationNode => None,
+            AnnounceKind::Node | AnnounceKind::Peer => dest
+                .app_data
+                .as_ref()
+                .and_then(|data| parse_display_name(data)),
+        };
+        if let (AnnounceKind::Node, Some(name)) = (kind, &name) {
+            self.registry.write().await.rename(&dest.address, name);
         }
+
+        let announce = self.discovered.write().await.record(Sighting {
+            hash: dest.address,
+            kind,
+            name,
+            hops: event.hops,
+            interface: event.interface,
+        });
+        let _ = self.announce_tx.send(announce);
     }
 
     pub async fn registry_mut(&self) -> tokio::sync::RwLockWriteGuard<'_, NodeRegistry> {
@@ -81,8 +81,8 @@ impl Clone for NetworkClient {
     fn clone(&self) -> Self {
         Self {
             registry: self.registry.clone(),
-            known_addresses: self.known_addresses.clone(),
-            node_announce_tx: self.node_announce_tx.clone(),
+            discovered: self.discovered.clone(),
+            announce_tx: self.announce_tx.clone(),
         }
     }
 }
@@ -0,0 +1,144 @@
+use crate::network::types::{Announce, AnnounceKind, NodeInfo, Sighting};
+use rinse::Address;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DISCOVERY_PATH: &str = ".rinse/discovered.toml";
+
+#[derive(Default, Serialize, Deserialize)]
+struct DiscoveryFile {
+    destinations: Vec<Announce>,
+}
+
+/// Every destination heard on the network, kept across restarts.
+pub struct DiscoveryStore {
+    path: PathBuf,
+    destinations: HashMap<Address, Announce>,
+}
+
+impl DiscoveryStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let destinations = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| toml::from_str::<DiscoveryFile>(&c).ok())
+            .map(|f| f.destinations.into_iter().map(|a| (a.hash, a)).collect())
+            .unwrap_or_default();
+        Self { path, destinations }
+    }
+
+    pub fn all(&self) -> Vec<Announce> {
+        self.destinations.values().cloned().collect()
+    }
+
+    /// Records one announce and returns the updated entry.
+    pub fn record(&mut self, sighting: Sighting) -> Announce {
+        let now = chrono::Utc::now().timestamp();
+        let entry = match self.destinations.get_mut(&sighting.hash) {
+            Some(entry) => {
+                entry.update(sighting, now);
+                entry.clone()
+            }
+            None => {
+                let entry = Announce::new(sighting, now);
+                self.destinations.insert(entry.hash, entry.clone());
+                entry
+            }
+        };
+        self.persist();
+        entry
+    }
+
+    /// Adds nodes heard before there was a discovery store. Their announces
+    /// weren't recorded, so they start with none and the time of import.
+    pub fn import(&mut self, nodes: Vec<NodeInfo>) {
+        let now = chrono::Utc::now().timestamp();
+        let mut imported = false;
+        for node in nodes {
+            if self.destinations.contains_key(&node.hash) {
+                continue;
+            }
+            let entry = Announce {
+                hash: node.hash,
+                kind: AnnounceKind::Node,
+                name: Some(node.name),
+                first_seen: now,
+                last_seen: now,
+                announce_count: 0,
+                hops: None,
+                interface: None,
+            };
+            self.destinations.insert(entry.hash, entry);
+            imported = true;
+        }
+        if imported {
+            self.persist();
+        }
+    }
+
+    fn persist(&self) {
+        let mut destinations: Vec<Announce> = self.destinations.values().cloned().collect();
+        destinations.sort_by_key(|a| a.first_seen);
+        let file = DiscoveryFile { destinations };
+
+        if let Ok(contents) = toml::to_string_pretty(&file) {
+            let _ = fs::write(&self.path, contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sighting(name: Option<&str>, hops: u8) -> Sighting {
+        Sighting {
+            hash: [7; 16],
+            kind: AnnounceKind::Node,
+            name: name.map(str::to_string),
+            hops,
+            interface: "tcp-client".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_survives_reload() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("discovered.toml");
+        let mut store = DiscoveryStore::new(&path);
+        store.record(sighting(Some("library"), 3));
+        let entry = store.record(sighting(None, 2));
+        assert_eq!(entry.announce_count, 2);
+        assert_eq!(entry.name.as_deref(), Some("library"));
+        assert_eq!(entry.hops, Some(2));
+        assert_eq!(entry.interface.as_deref(), Some("tcp-client"));
+
+        let reloaded = DiscoveryStore::new(&path).all();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].announce_count, 2);
+        assert_eq!(reloaded[0].first_seen, entry.first_seen);
+    }
+
+    #[test]
+    fn test_import_keeps_recorded_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut store = DiscoveryStore::new(tmp.path().join("discovered.toml"));
+        store.record(sighting(Some("library"), 1));
+        let node = |byte, name: &str| NodeInfo {
+            hash: [byte; 16],
+            name: name.to_string(),
+            identify: false,
+        };
+        store.import(vec![node(7, "old name"), node(8, "heard")]);
+
+        let mut all = store.all();
+        all.sort_by_key(|a| a.hash);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].name.as_deref(), Some("library"));
+        assert_eq!(all[0].announce_count, 1);
+        assert_eq!(all[1].name.as_deref(), Some("heard"));
+        assert_eq!(all[1].announce_count, 0);
+    }
+}
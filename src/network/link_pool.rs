@@ -0,0 +1,238 @@
+use rinse::Address;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+struct PooledLink<L> {
+    link: L,
+    identified: bool,
+    last_used: Instant,
+}
+
+/// Established links kept around per destination so consecutive page,
+/// partial and download requests skip the path request and link handshake.
+///
+/// Each request checks a link out with `get` or `insert` and hands it back
+/// with `release`. Links leaving the pool only come out of `take_stale` for
+/// closing once no request holds them.
+pub struct LinkPool<L> {
+    idle_timeout: Duration,
+    links: HashMap<Address, PooledLink<L>>,
+    /// Requests currently holding each link, pooled or not.
+    users: Vec<(L, usize)>,
+    /// Links dropped from the pool that still need closing on the node.
+    stale: Vec<L>,
+    /// One gate per destination being connected, so concurrent misses wait
+    /// for the first handshake instead of each opening their own link.
+    connecting: HashMap<Address, Arc<Mutex<()>>>,
+}
+
+impl<L: Copy + PartialEq> LinkPool<L> {
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            idle_timeout,
+            links: HashMap::new(),
+            users: Vec::new(),
+            stale: Vec::new(),
+            connecting: HashMap::new(),
+        }
+    }
+
+    /// Checks out a live link to `dest`, returning it and whether it still
+    /// needs to identify.
+    ///
+    /// A link we already identified on can't be made anonymous again, so it is
+    /// dropped when identification has since been turned off.
+    pub fn get(&mut self, dest: &Address, identify: bool) -> Option<(L, bool)> {
+        self.prune_idle();
+
+        let entry = self.links.get_mut(dest)?;
+        if entry.identified && !identify {
+            let link = entry.link;
+            self.evict(dest, link);
+            return None;
+        }
+
+        entry.last_used = Instant::now();
+        let checked_out = (entry.link, identify && !entry.identified);
+        self.checkout(checked_out.0);
+        Some(checked_out)
+    }
+
+    /// Pools a freshly established link, checked out to the caller.
+    pub fn insert(&mut self, dest: Address, link: L) {
+        let replaced = self.links.insert(
+            dest,
+            PooledLink {
+                link,
+                identified: false,
+                last_used: Instant::now(),
+            },
+        );
+        self.stale.extend(replaced.map(|entry| entry.link));
+        self.checkout(link);
+    }
+
+    pub fn mark_identified(&mut self, dest: &Address) {
+        if let Some(entry) = self.links.get_mut(dest) {
+            entry.identified = true;
+        }
+    }
+
+    /// Hands back a link from `get` or `insert`.
+    pub fn release(&mut self, link: L) {
+        if let Some(idx) = self.users.iter().position(|(l, _)| *l == link) {
+            self.users[idx].1 -= 1;
+            if self.users[idx].1 == 0 {
+                self.users.swap_remove(idx);
+            }
+        }
+        for entry in self.links.values_mut().filter(|e| e.link == link) {
+            entry.last_used = Instant::now();
+        }
+    }
+
+    /// Drops `link` from the pool if it is still the one kept for `dest`.
+    /// It is closed once the requests still holding it release it.
+    pub fn evict(&mut self, dest: &Address, link: L) {
+        if self.links.get(dest).is_some_and(|entry| entry.link == link) {
+            self.links.remove(dest);
+            self.stale.push(link);
+        }
+    }
+
+    /// Releases a link whose request was given up on. When nothing else is
+    /// using it the link is evicted too, so closing it stops the transfer.
+    pub fn abandon(&mut self, dest: &Address, link: L) {
+        self.release(link);
+        if !self.in_use(link) {
+            self.evict(dest, link);
+        }
+    }
+
+    /// Links evicted, expired or replaced that no request holds any more.
+    pub fn take_stale(&mut self) -> Vec<L> {
+        let (idle, busy) = std::mem::take(&mut self.stale)
+            .into_iter()
+            .partition(|link| !self.in_use(*link));
+        self.stale = busy;
+        idle
+    }
+
+    /// The gate to hold while opening a link to `dest`. Check the pool again
+    /// once it is acquired; another request may have connected meanwhile.
+    pub fn connect_gate(&mut self, dest: Address) -> Arc<Mutex<()>> {
+        self.connecting
+            .retain(|_, gate| Arc::strong_count(gate) > 1);
+        self.connecting.entry(dest).or_default().clone()
+    }
+
+    pub fn prune_idle(&mut self) {
+        let idle_timeout = self.idle_timeout;
+        let users = &self.users;
+        let stale = &mut self.stale;
+        self.links.retain(|_, entry| {
+            let keep = entry.last_used.elapsed() < idle_timeout
+                || users.iter().any(|(l, _)| *l == entry.link);
+            if !keep {
+                stale.push(entry.link);
+            }
+            keep
+        });
+    }
+
+    fn checkout(&mut self, link: L) {
+        match self.users.iter_mut().find(|(l, _)| *l == link) {
+            Some((_, count)) => *count += 1,
+            None => self.users.push((link, 1)),
+        }
+    }
+
+    fn in_use(&self, link: L) -> bool {
+        self.users.iter().any(|(l, _)| *l == link)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuse_and_identify() {
+        let mut pool = LinkPool::new(Duration::from_secs(60));
+        let dest = [1; 16];
+
+        assert!(pool.get(&dest, false).is_none());
+        pool.insert(dest, 7u32);
+        pool.release(7);
+        assert_eq!(pool.get(&dest, false), Some((7, false)));
+        pool.release(7);
+
+        assert_eq!(pool.get(&dest, true), Some((7, true)));
+        pool.mark_identified(&dest);
+        pool.release(7);
+        assert_eq!(pool.get(&dest, true), Some((7, false)));
+        pool.release(7);
+
+        assert!(pool.get(&dest, false).is_none());
+        assert!(pool.get(&dest, true).is_none());
+        assert_eq!(pool.take_stale(), [7]);
+    }
+
+    #[test]
+    fn test_idle_links_expire() {
+        let mut pool = LinkPool::new(Duration::ZERO);
+        let dest = [2; 16];
+        pool.insert(dest, 1u32);
+        // Never expired while a request holds it.
+        pool.prune_idle();
+        assert!(pool.take_stale().is_empty());
+        pool.release(1);
+        assert!(pool.get(&dest, false).is_none());
+        assert_eq!(pool.take_stale(), [1]);
+        assert!(pool.take_stale().is_empty());
+    }
+
+    #[test]
+    fn test_links_in_use_stay_open() {
+        let mut pool = LinkPool::new(Duration::from_secs(60));
+        let dest = [4; 16];
+        pool.insert(dest, 1u32);
+        assert_eq!(pool.get(&dest, false), Some((1, false)));
+
+        // One request gives up while the other is still waiting on the link.
+        pool.abandon(&dest, 1);
+        assert!(pool.take_stale().is_empty());
+        assert_eq!(pool.get(&dest, false), Some((1, false)));
+        pool.release(1);
+
+        // A failure on an old link leaves its replacement alone.
+        pool.evict(&dest, 2);
+        pool.evict(&dest, 1);
+        assert!(pool.take_stale().is_empty());
+        pool.release(1);
+        assert_eq!(pool.take_stale(), [1]);
+        assert!(pool.get(&dest, false).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_share_a_gate() {
+        let mut pool = LinkPool::<u32>::new(Duration::from_secs(60));
+        let dest = [3; 16];
+        let first = pool.connect_gate(dest);
+        let held = first.lock().await;
+        let second = pool.connect_gate(dest);
+        assert!(second.try_lock().is_err());
+        drop(held);
+        drop((first, second));
+        assert!(pool.connect_gate(dest).try_lock().is_ok());
+
+        pool.insert(dest, 1);
+        pool.insert(dest, 2);
+        pool.release(2);
+        assert!(pool.take_stale().is_empty());
+        pool.release(1);
+        assert_eq!(pool.take_stale(), [1]);
+    }
+}
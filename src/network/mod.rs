@@ -1,7 +1,15 @@
+pub mod bridge;
 mod client;
+mod discovery_store;
+mod link_pool;
 mod node_registry;
+pub mod supervisor;
+pub mod tcp_server;
+pub mod traffic;
 mod types;
 
 pub use client::NetworkClient;
+pub use discovery_store::{DiscoveryStore, DISCOVERY_PATH};
+pub use link_pool::LinkPool;
 pub use node_registry::NodeRegistry;
-pub use types::NodeInfo;
+pub use types::{split_page_url, Announce, AnnounceKind, NodeInfo};
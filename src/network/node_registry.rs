@@ -1,51 +1,105 @@
 use crate::network::types::NodeInfo;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Default, Serialize, Deserialize)]
 struct NodesFile {
+    #[serde(default)]
+    saved: Vec<NodeInfo>,
+    /// Files written before favorites were split out kept every node here.
+    #[serde(default, skip_serializing)]
     nodes: Vec<NodeInfo>,
 }
 
+/// The user's saved nodes. Everything heard on the network lives in the
+/// discovery store; announces only ever rename a saved node here.
 pub struct NodeRegistry {
     path: PathBuf,
-    nodes: HashMap<[u8; 16], NodeInfo>,
+    saved: HashMap<[u8; 16], NodeInfo>,
+    /// Nodes from an old file that the migration didn't keep.
+    unsaved: Vec<NodeInfo>,
 }
 
 impl NodeRegistry {
-    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+    /// `bookmarked` holds the nodes the user has bookmarked pages on, used
+    /// to migrate an old `nodes.toml`.
+    pub fn new<P: AsRef<Path>>(path: P, bookmarked: &HashSet<[u8; 16]>) -> Self {
         let path = path.as_ref().to_path_buf();
-        let nodes = Self::load_from_path(&path);
-        Self { path, nodes }
-    }
+        let file: NodesFile = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| toml::from_str(&c).ok())
+            .unwrap_or_default();
 
-    fn load_from_path(path: &Path) -> HashMap<[u8; 16], NodeInfo> {
-        let contents = match fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return HashMap::new(),
+        let mut registry = Self {
+            path,
+            saved: file.saved.into_iter().map(|n| (n.hash, n)).collect(),
+            unsaved: Vec::new(),
         };
+        if !file.nodes.is_empty() {
+            registry.migrate(file.nodes, bookmarked);
+        }
+        registry
+    }
 
-        let file: NodesFile = match toml::from_str(&contents) {
-            Ok(f) => f,
-            Err(_) => return HashMap::new(),
-        };
+    /// The old file mixed saved nodes with everything ever announced. Only
+    /// nodes the user evidently curated, with self-identify turned on or a
+    /// bookmarked page, stay saved; the rest are left for
+    /// [`take_unsaved`](Self::take_unsaved). The old file is kept as `.bak`.
+    fn migrate(&mut self, nodes: Vec<NodeInfo>, bookmarked: &HashSet<[u8; 16]>) {
+        let backup = self.path.with_extension("toml.bak");
+        if let Err(e) = fs::copy(&self.path, &backup) {
+            log::warn!("Failed to back up {}: {}", self.path.display(), e);
+        }
+
+        for node in nodes {
+            if self.saved.contains_key(&node.hash) {
+                continue;
+            }
+            if node.identify || bookmarked.contains(&node.hash) {
+                self.saved.insert(node.hash, node);
+            } else {
+                self.unsaved.push(node);
+            }
+        }
+        log::info!(
+            "Migrated {}: kept {} saved node(s), moved {} to discovery",
+            self.path.display(),
+            self.saved.len(),
+            self.unsaved.len()
+        );
+        self.persist();
+    }
 
-        file.nodes.into_iter().map(|n| (n.hash, n)).collect()
+    /// Hands over the nodes an old file listed but the migration didn't
+    /// keep, so they can go into the discovery store.
+    pub fn take_unsaved(&mut self) -> Vec<NodeInfo> {
+        std::mem::take(&mut self.unsaved)
     }
 
+    /// Adds a favorite. Only ever called for an explicit save.
     pub fn save(&mut self, node: NodeInfo) {
-        self.nodes.insert(node.hash, node);
+        self.saved.insert(node.hash, node);
         self.persist();
     }
 
-    pub fn all(&self) -> Vec<&NodeInfo> {
-        self.nodes.values().collect()
+    /// Picks up the name a saved node last announced. Writes the file only
+    /// when a saved node's name actually changes.
+    pub fn rename(&mut self, hash: &[u8; 16], name: &str) {
+        match self.saved.get_mut(hash) {
+            Some(saved) if saved.name != name => saved.name = name.to_string(),
+            _ => return,
+        }
+        self.persist();
+    }
+
+    pub fn saved(&self) -> Vec<&NodeInfo> {
+        self.saved.values().collect()
     }
 
     pub fn toggle_identify(&mut self, hash: &[u8; 16]) -> Option<bool> {
-        let new_value = if let Some(node) = self.nodes.get_mut(hash) {
+        let new_value = if let Some(node) = self.saved.get_mut(hash) {
             node.identify = !node.identify;
             Some(node.identify)
         } else {
@@ -58,7 +112,7 @@ impl NodeRegistry {
     }
 
     pub fn remove(&mut self, hash: &[u8; 16]) -> Option<NodeInfo> {
-        let removed = self.nodes.remove(hash);
+        let removed = self.saved.remove(hash);
         if removed.is_some() {
             self.persist();
         }
@@ -67,7 +121,8 @@ impl NodeRegistry {
 
     fn persist(&self) {
         let file = NodesFile {
-            nodes: self.nodes.values().cloned().collect(),
+            saved: self.saved.values().cloned().collect(),
+            nodes: Vec::new(),
         };
 
         if let Ok(contents) = toml::to_string_pretty(&file) {
@@ -75,3 +130,58 @@ impl NodeRegistry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(byte: u8, name: &str, identify: bool) -> NodeInfo {
+        NodeInfo {
+            hash: [byte; 16],
+            name: name.to_string(),
+            identify,
+        }
+    }
+
+    fn names(nodes: Vec<&NodeInfo>) -> Vec<String> {
+        let mut names: Vec<String> = nodes.iter().map(|n| n.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_migration_keeps_curated_nodes_and_announces_only_rename() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("nodes.toml");
+        let old = r#"
+[[nodes]]
+hash = "01010101010101010101010101010101"
+name = "mine"
+identify = true
+
+[[nodes]]
+hash = "02020202020202020202020202020202"
+name = "bookmarked"
+
+[[nodes]]
+hash = "04040404040404040404040404040404"
+name = "heard"
+"#;
+        fs::write(&path, old).unwrap();
+
+        let mut registry = NodeRegistry::new(&path, &HashSet::from([[2; 16]]));
+        assert!(tmp.path().join("nodes.toml.bak").exists());
+        assert_eq!(names(registry.saved()), ["bookmarked", "mine"]);
+        assert_eq!(names(registry.take_unsaved().iter().collect()), ["heard"]);
+
+        registry.rename(&[3; 16], "new");
+        registry.rename(&[1; 16], "renamed");
+        registry.remove(&[2; 16]);
+        registry.rename(&[2; 16], "renamed again");
+        assert_eq!(names(registry.saved()), ["renamed"]);
+
+        registry.save(node(3, "new", false));
+        let reloaded = NodeRegistry::new(&path, &HashSet::new());
+        assert_eq!(names(reloaded.saved()), ["new", "renamed"]);
+    }
+}
@@ -0,0 +1,222 @@
+use rand::Rng;
+use std::future::Future;
+use std::io;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+pub const MIN_RETRY_DELAY: Duration = Duration::from_secs(2);
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Exponential backoff between reconnect attempts.
+pub struct Backoff {
+    min: Duration,
+    max: Duration,
+    failures: u32,
+}
+
+impl Backoff {
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self {
+            min,
+            max,
+            failures: 0,
+        }
+    }
+
+    /// Doubles from `min` up to `max`, then shaves up to half off at random
+    /// so interfaces that dropped together don't all retry together.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self
+            .min
+            .saturating_mul(2u32.saturating_pow(self.failures))
+            .min(self.max);
+        self.failures = self.failures.saturating_add(1);
+        delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(MIN_RETRY_DELAY, MAX_RETRY_DELAY)
+    }
+}
+
+/// A reconnect waiting to happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retry {
+    /// Failures in a row, counting links that dropped before settling.
+    pub attempt: u32,
+    pub at: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkState {
+    Connecting,
+    Connected,
+    Retrying(Retry),
+}
+
+/// Keeps a link up for as long as the task runs. `connect` opens the link and
+/// returns a future that finishes when it drops; every failure or drop waits
+/// out the next backoff delay. The backoff only resets once a link has stayed
+/// up longer than the longest delay, so a flapping peer isn't hammered.
+pub async fn supervise<F, Fut, S>(
+    mut connect: F,
+    mut backoff: Backoff,
+    state: watch::Sender<LinkState>,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = io::Result<S>>,
+    S: Future<Output = io::Result<()>>,
+{
+    loop {
+        state.send_replace(LinkState::Connecting);
+        match connect().await {
+            Ok(link) => {
+                state.send_replace(LinkState::Connected);
+                let since = Instant::now();
+                if let Err(e) = link.await {
+                    log::warn!("Link dropped: {}", e);
+                }
+                if since.elapsed() > backoff.max {
+                    backoff.reset();
+                }
+            }
+            Err(e) => log::warn!("Connect failed: {}", e),
+        }
+
+        let delay = backoff.next_delay();
+        state.send_replace(LinkState::Retrying(Retry {
+            attempt: backoff.failures(),
+            at: Instant::now() + delay,
+        }));
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::bridge;
+    use crate::network::traffic::Traffic;
+    use std::sync::Arc;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn test_backoff_doubles_with_jitter_up_to_max() {
+        let mut backoff = Backoff::new(Duration::from_secs(2), Duration::from_secs(10));
+        for full in [2, 4, 8, 10, 10] {
+            let delay = backoff.next_delay();
+            let full = Duration::from_secs(full);
+            assert!(delay <= full && delay >= full / 2, "{:?}", delay);
+        }
+        assert_eq!(backoff.failures(), 5);
+        backoff.reset();
+        assert!(backoff.next_delay() <= Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_to_flapping_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Hang up on the first three connections, then stay up.
+        tokio::spawn(async move {
+            let mut kept = Vec::new();
+            for n in 0.. {
+                let (stream, _) = listener.accept().await.unwrap();
+                if n >= 3 {
+                    kept.push(stream);
+                }
+            }
+        });
+
+        let connect = move || async move {
+            let mut stream = TcpStream::connect(addr).await?;
+            Ok(async move {
+                let mut buf = Vec::new();
+                stream.read_to_end(&mut buf).await.map(|_| ())
+            })
+        };
+        let backoff = Backoff::new(Duration::from_millis(5), Duration::from_millis(40));
+        let (state_tx, mut state_rx) = watch::channel(LinkState::Connecting);
+        let task = tokio::spawn(supervise(connect, backoff, state_tx));
+
+        let mut attempts = Vec::new();
+        let settled = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                state_rx.changed().await.unwrap();
+                let state = *state_rx.borrow_and_update();
+                if let LinkState::Retrying(retry) = state {
+                    attempts.push(retry.attempt);
+                }
+                if state == LinkState::Connected && attempts.len() == 3 {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    return *state_rx.borrow();
+                }
+            }
+        })
+        .await
+        .unwrap();
+        task.abort();
+
+        assert_eq!(attempts, [1, 2, 3]);
+        assert_eq!(settled, LinkState::Connected);
+    }
+
+    #[tokio::test]
+    async fn test_bridged_link_retries_when_peer_hangs_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Hang up on the first connection, then stay up.
+        tokio::spawn(async move {
+            let mut kept = Vec::new();
+            for n in 0.. {
+                let (stream, _) = listener.accept().await.unwrap();
+                if n >= 1 {
+                    kept.push(stream);
+                }
+            }
+        });
+        // Stands in for the node's end of the bridge, which never closes.
+        let node_side = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node_addr = node_side.local_addr().unwrap();
+        let mut node_ends = Vec::new();
+
+        let connect = move || async move {
+            let remote = TcpStream::connect(addr).await?;
+            let local = TcpStream::connect(node_addr).await?;
+            Ok(bridge::pipe(remote, local, Arc::new(Traffic::default())))
+        };
+        let backoff = Backoff::new(Duration::from_millis(5), Duration::from_millis(40));
+        let (state_tx, mut state_rx) = watch::channel(LinkState::Connecting);
+        let task = tokio::spawn(supervise(connect, backoff, state_tx));
+
+        let mut retries = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                tokio::select! {
+                    Ok((stream, _)) = node_side.accept() => node_ends.push(stream),
+                    Ok(()) = state_rx.changed() => match *state_rx.borrow_and_update() {
+                        LinkState::Retrying(retry) => retries.push(retry.attempt),
+                        LinkState::Connected if !retries.is_empty() => break,
+                        _ => {}
+                    },
+                }
+            }
+        })
+        .await
+        .unwrap();
+        task.abort();
+
+        assert_eq!(retries, [1]);
+    }
+}
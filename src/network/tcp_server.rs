@@ -0,0 +1,52 @@
+use crate::network::bridge;
+use crate::network::traffic::Traffic;
+use rinse::{Node, TcpTransport};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+/// Accepts peers on `listener`, each becoming its own interface on the node,
+/// and publishes the addresses of connected peers on `clients`. Traffic from
+/// every peer is counted together. Dropping the future disconnects them all.
+pub async fn serve(
+    listener: TcpListener,
+    node: Node<TcpTransport>,
+    clients: watch::Sender<Vec<SocketAddr>>,
+    traffic: Arc<Traffic>,
+) {
+    let mut peers = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, addr)) => {
+                    log::info!("TCP server accepted {}", addr);
+                    clients.send_modify(|c| c.push(addr));
+                    let node = node.clone();
+                    let traffic = traffic.clone();
+                    peers.spawn(async move {
+                        let result = match bridge::attach(&node, stream, traffic).await {
+                            Ok(pipe) => pipe.await,
+                            Err(e) => Err(e),
+                        };
+                        if let Err(e) = result {
+                            log::warn!("TCP peer {} failed: {}", addr, e);
+                        }
+                        addr
+                    });
+                }
+                Err(e) => {
+                    // Usually out of file descriptors; back off instead of spinning.
+                    log::warn!("TCP server accept failed: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            },
+            Some(Ok(addr)) = peers.join_next() => {
+                log::info!("TCP peer {} disconnected", addr);
+                clients.send_modify(|c| c.retain(|a| *a != addr));
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::atomic::{AtomicI64, AtomicU32, AtomicU64, Ordering};
+
+/// Reticulum's TCP interfaces frame packets with HDLC.
+const FLAG: u8 = 0x7e;
+const ESCAPE: u8 = 0x7d;
+const ESCAPE_MASK: u8 = 0x20;
+/// Low bits of a packet's first header byte give its type.
+const PACKET_TYPE_MASK: u8 = 0x03;
+const PACKET_TYPE_ANNOUNCE: u8 = 0x01;
+
+/// What an interface has carried, as last reported to the TUI.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InterfaceStats {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub packets_in: u64,
+    pub packets_out: u64,
+    pub announces_heard: u64,
+    /// Unix time of the last byte either way.
+    pub last_activity: Option<i64>,
+    /// Unix time the link came up, or the server started listening.
+    pub up_since: Option<i64>,
+    /// Times a client link came back after dropping.
+    pub reconnects: u32,
+}
+
+/// Live counters for one interface, shared by the tasks carrying its traffic.
+#[derive(Default)]
+pub struct Traffic {
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    packets_in: AtomicU64,
+    packets_out: AtomicU64,
+    announces_heard: AtomicU64,
+    last_activity: AtomicI64,
+    up_since: AtomicI64,
+    connects: AtomicU32,
+}
+
+impl Traffic {
+    /// Counts bytes read from the peer and the packets they complete.
+    pub fn received(&self, data: &[u8], frames: &mut FrameCounter) {
+        self.bytes_in
+            .fetch_add(data.len() as u64, Ordering::Relaxed);
+        frames.feed(data, |header| {
+            self.packets_in.fetch_add(1, Ordering::Relaxed);
+            if header & PACKET_TYPE_MASK == PACKET_TYPE_ANNOUNCE {
+                self.announces_heard.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        self.touch();
+    }
+
+    pub fn sent(&self, data: &[u8], frames: &mut FrameCounter) {
+        self.bytes_out
+            .fetch_add(data.len() as u64, Ordering::Relaxed);
+        frames.feed(data, |_| {
+            self.packets_out.fetch_add(1, Ordering::Relaxed);
+        });
+        self.touch();
+    }
+
+    pub fn link_up(&self) {
+        self.up_since
+            .store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+        self.connects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn link_down(&self) {
+        self.up_since.store(0, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> InterfaceStats {
+        let time = |t: &AtomicI64| Some(t.load(Ordering::Relaxed)).filter(|&t| t > 0);
+        InterfaceStats {
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            packets_in: self.packets_in.load(Ordering::Relaxed),
+            packets_out: self.packets_out.load(Ordering::Relaxed),
+            announces_heard: self.announces_heard.load(Ordering::Relaxed),
+            last_activity: time(&self.last_activity),
+            up_since: time(&self.up_since),
+            reconnects: self.connects.load(Ordering::Relaxed).saturating_sub(1),
+        }
+    }
+
+    fn touch(&self) {
+        self.last_activity
+            .store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+    }
+}
+
+/// Follows HDLC framing across reads in one direction of a stream.
+#[derive(Default)]
+pub struct FrameCounter {
+    header: Option<u8>,
+    escaped: bool,
+}
+
+impl FrameCounter {
+    /// Calls `on_packet` with the first byte of each packet that ends in `data`.
+    pub fn feed(&mut self, data: &[u8], mut on_packet: impl FnMut(u8)) {
+        for &byte in data {
+            match byte {
+                FLAG => {
+                    if let Some(header) = self.header.take() {
+                        on_packet(header);
+                    }
+                    self.escaped = false;
+                }
+                ESCAPE => self.escaped = true,
+                _ => {
+                    let byte = if self.escaped {
+                        byte ^ ESCAPE_MASK
+                    } else {
+                        byte
+                    };
+                    self.escaped = false;
+                    self.header.get_or_insert(byte);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_packets_and_announces_across_reads() {
+        let traffic = Traffic::default();
+        let mut frames = FrameCounter::default();
+        // An announce, then a data packet whose header byte is escaped and
+        // whose closing flag arrives in a later read.
+        traffic.received(&[FLAG, 0x01, 0x00, 0xaa, FLAG, FLAG, ESCAPE], &mut frames);
+        traffic.received(&[0x5e, 0x01, FLAG], &mut frames);
+
+        let stats = traffic.snapshot();
+        assert_eq!(stats.bytes_in, 10);
+        assert_eq!(stats.packets_in, 2);
+        assert_eq!(stats.announces_heard, 1);
+        assert!(stats.last_activity.is_some());
+
+        traffic.link_up();
+        traffic.link_down();
+        traffic.link_up();
+        let stats = traffic.snapshot();
+        assert_eq!(stats.reconnects, 1);
+        assert!(stats.up_since.is_some());
+    }
+}
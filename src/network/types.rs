@@ -16,6 +16,125 @@ impl NodeInfo {
     }
 }
 
+/// What a destination announced itself as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnounceKind {
+    /// A `nomadnetwork.node` serving pages.
+    Node,
+    /// A `nomad.messages` destination that can receive direct messages.
+    Peer,
+    /// An `lxmf.propagation` node that holds messages for offline peers.
+    PropagationNode,
+}
+
+impl AnnounceKind {
+    pub const ALL: [AnnounceKind; 3] = [
+        AnnounceKind::Node,
+        AnnounceKind::Peer,
+        AnnounceKind::PropagationNode,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnnounceKind::Node => "Nodes",
+            AnnounceKind::Peer => "Peers",
+            AnnounceKind::PropagationNode => "Propagation Nodes",
+        }
+    }
+
+    pub fn badge(self) -> &'static str {
+        match self {
+            AnnounceKind::Node => "NODE",
+            AnnounceKind::Peer => "PEER",
+            AnnounceKind::PropagationNode => "PROP",
+        }
+    }
+}
+
+/// A destination as it was heard in one announce.
+#[derive(Debug, Clone)]
+pub struct Sighting {
+    pub hash: Address,
+    pub kind: AnnounceKind,
+    pub name: Option<String>,
+    pub hops: u8,
+    /// The interface the announce arrived on.
+    pub interface: String,
+}
+
+/// Everything we know about a destination heard on the network. `name` is
+/// `None` when it never announced a display name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Announce {
+    #[serde(with = "hex_bytes_16")]
+    pub hash: Address,
+    pub kind: AnnounceKind,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    pub announce_count: u64,
+    /// Hops and receiving interface of the latest announce.
+    #[serde(default)]
+    pub hops: Option<u8>,
+    #[serde(default)]
+    pub interface: Option<String>,
+}
+
+impl Announce {
+    pub fn new(sighting: Sighting, now: i64) -> Self {
+        Self {
+            hash: sighting.hash,
+            kind: sighting.kind,
+            name: sighting.name,
+            first_seen: now,
+            last_seen: now,
+            announce_count: 1,
+            hops: Some(sighting.hops),
+            interface: Some(sighting.interface),
+        }
+    }
+
+    /// Folds a later announce in, keeping what it didn't carry.
+    pub fn update(&mut self, sighting: Sighting, now: i64) {
+        self.kind = sighting.kind;
+        self.name = sighting.name.or(self.name.take());
+        self.last_seen = now;
+        self.announce_count += 1;
+        self.hops = Some(sighting.hops);
+        self.interface = Some(sighting.interface);
+    }
+
+    pub fn hash_hex(&self) -> String {
+        hex::encode(self.hash)
+    }
+
+    /// The name to show, falling back to the start of the hash.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("<{}>", &self.hash_hex()[..8]),
+        }
+    }
+
+    /// Nodes can be browsed; other kinds can't.
+    pub fn node_info(&self) -> Option<NodeInfo> {
+        (self.kind == AnnounceKind::Node).then(|| NodeInfo {
+            hash: self.hash,
+            name: self.display_name(),
+            identify: false,
+        })
+    }
+}
+
+/// Splits a `hash:/path` page URL into the destination and path.
+pub fn split_page_url(url: &str) -> Option<(Address, &str)> {
+    let (hash_hex, path) = url.split_once(':')?;
+    let hash = hex::decode(hash_hex).ok()?.try_into().ok()?;
+    Some((hash, path))
+}
+
 mod hex_bytes_16 {
     use serde::{Deserialize, Deserializer, Serializer};
 
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SETTINGS_PATH: &str = ".rinse/nomad.toml";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub hosting: HostingSettings,
+    pub links: LinkSettings,
+    pub cache: CacheSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HostingSettings {
+    pub pages_dir: PathBuf,
+    pub files_dir: PathBuf,
+    /// How long an executable page may run before it is killed.
+    pub script_timeout_secs: u64,
+    /// Largest stdout an executable page may produce, in bytes.
+    pub max_script_output: usize,
+    /// Hex identity hashes that may not read anything we host.
+    pub blocked_identities: Vec<String>,
+    /// Micron page shown to peers that fail an access check.
+    pub access_denied_page: Option<PathBuf>,
+}
+
+impl Default for HostingSettings {
+    fn default() -> Self {
+        Self {
+            pages_dir: PathBuf::from(".rinse/pages"),
+            files_dir: PathBuf::from(".rinse/files"),
+            script_timeout_secs: 30,
+            max_script_output: 512 * 1024,
+            blocked_identities: Vec::new(),
+            access_denied_page: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkSettings {
+    /// How long an unused link to a node is kept for reuse.
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for LinkSettings {
+    fn default() -> Self {
+        Self {
+            idle_timeout_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    pub dir: PathBuf,
+    /// Cached pages older than this are dropped at startup.
+    pub max_age_days: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(".rinse/cache"),
+            max_age_days: 30,
+        }
+    }
+}
+
+impl Settings {
+    /// Loads nomad's own settings, writing the defaults out on first run so
+    /// users have a file to edit.
+    pub fn load() -> Self {
+        Self::load_from_path(SETTINGS_PATH)
+    }
+
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let contents = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => {
+                let settings = Self::default();
+                settings.persist(path);
+                return settings;
+            }
+        };
+
+        match toml::from_str(&contents) {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::warn!("Failed to parse {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    fn persist(&self, path: &Path) {
+        if let Ok(contents) = toml::to_string_pretty(self) {
+            let _ = fs::write(path, contents);
+        }
+    }
+}
@@ -20,48 +20,86 @@ use ratatui::{
     widgets::Paragraph,
     Terminal,
 };
+use rinse::config::InterfaceConfig;
 use tokio::sync::mpsc;
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
+use super::browser_tabs::{BrowserTabs, TabStripAction};
 use super::browser_view::BrowserView;
+use super::conversations::{ConversationClick, ConversationsView};
 use super::discovery::{DiscoveryView, ModalAction};
-use super::interfaces::{InterfaceInfo, InterfacesView};
+use super::downloads::{DownloadAction, DownloadState, DownloadsView};
+use super::history::{HistoryAction, HistoryView};
+use super::interfaces::{FormField, InterfaceForm, InterfaceInfo, InterfaceKind, InterfacesView};
+use super::link_hints::{HintOutcome, LinkHints};
 use super::modal::{Modal, ModalButton};
 use super::mynode::MyNodeView;
+use super::page_source::PageEnvelope;
 use super::saved::{SavedModalAction, SavedView};
 use super::status_bar::StatusBar;
 use super::tabs::{Tab, TabBar};
 
-use crate::network::NodeInfo;
+use crate::bookmarks::Bookmark;
+use crate::downloads::{check_filename, open_folder, unique_path, TargetError, DOWNLOADS_DIR};
+use crate::history::{page_title, HistoryEntry};
+use crate::messages::{Conversation, DirectMessage, MessageState, StoredMessage};
+use crate::network::supervisor::Retry;
+use crate::network::traffic::InterfaceStats;
+use crate::network::{split_page_url, Announce, NodeInfo};
 
 #[derive(Debug, Clone)]
 pub enum NetworkEvent {
-    NodeAnnounce(NodeInfo),
+    Announce(Announce),
     AnnounceSent,
     Status(String),
     PageReceived {
+        request_id: u64,
         url: String,
         data: Vec<u8>,
+        round_trip_ms: u64,
+    },
+    PageCached {
+        request_id: u64,
+        url: String,
+        data: Vec<u8>,
+        fetched_at: i64,
     },
     PageFailed {
+        request_id: u64,
         url: String,
         reason: String,
     },
     PartialReceived {
+        request_id: u64,
+        partial: micronaut::PartialInfo,
+        data: Vec<u8>,
+    },
+    PartialCached {
+        request_id: u64,
         partial: micronaut::PartialInfo,
         data: Vec<u8>,
     },
     PartialFailed {
+        request_id: u64,
         partial: micronaut::PartialInfo,
         reason: String,
     },
+    DownloadStarted {
+        transfer_id: u64,
+        resumed_from: u64,
+    },
+    DownloadProgress {
+        transfer_id: u64,
+        received_bytes: usize,
+        total_bytes: usize,
+    },
     DownloadComplete {
-        filename: String,
+        transfer_id: u64,
         path: String,
     },
     DownloadFailed {
-        filename: String,
+        transfer_id: u64,
         reason: String,
     },
     RelayStats(rinse::StatsSnapshot),
@@ -72,6 +110,33 @@ pub enum NetworkEvent {
     InterfaceStatus {
         name: String,
         connected: bool,
+        retry: Option<Retry>,
+    },
+    /// An interface's counters, sent every second while it runs.
+    InterfaceTraffic {
+        name: String,
+        stats: InterfaceStats,
+    },
+    /// Every configured interface, sent after the config changes.
+    Interfaces(Vec<InterfaceInfo>),
+    /// Addresses of the peers connected to a TCP server interface.
+    InterfaceClients {
+        name: String,
+        clients: Vec<String>,
+    },
+    MessageReceived {
+        peer: [u8; 16],
+        message: StoredMessage,
+    },
+    MessageSent {
+        peer: [u8; 16],
+        id: String,
+        state: MessageState,
+    },
+    MessageFailed {
+        peer: [u8; 16],
+        id: String,
+        reason: String,
     },
 }
 
@@ -79,23 +144,45 @@ pub enum NetworkEvent {
 pub enum TuiCommand {
     Announce,
     FetchPage {
+        request_id: u64,
         node: NodeInfo,
         path: String,
         form_data: std::collections::HashMap<String, String>,
     },
     FetchPartial {
+        request_id: u64,
         node: NodeInfo,
         partial: micronaut::PartialInfo,
         form_data: std::collections::HashMap<String, String>,
     },
     DownloadFile {
+        request_id: u64,
         node: NodeInfo,
         path: String,
         filename: String,
+        dir: std::path::PathBuf,
+    },
+    CancelRequest {
+        request_id: u64,
+    },
+    CancelDownload {
+        transfer_id: u64,
+        /// Delete the partial file instead of keeping it to resume later.
+        discard: bool,
     },
     Reconnect {
         name: String,
     },
+    /// Writes an interface to config.toml and restarts it, replacing the one
+    /// called `original` when editing.
+    SaveInterface {
+        original: Option<String>,
+        name: String,
+        config: InterfaceConfig,
+    },
+    DeleteInterface {
+        name: String,
+    },
     SaveNode {
         node: NodeInfo,
     },
@@ -105,21 +192,56 @@ pub enum TuiCommand {
     ToggleNodeIdentify {
         hash: [u8; 16],
     },
+    RecordHistory {
+        entry: HistoryEntry,
+    },
+    SaveBookmark {
+        bookmark: Bookmark,
+    },
+    RemoveBookmark {
+        url: String,
+    },
+    SendMessage {
+        message: DirectMessage,
+    },
+    MarkConversationRead {
+        peer: [u8; 16],
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum AppMode {
     Normal,
-    Editing { field_name: String, masked: bool },
+    Editing {
+        field_name: String,
+        masked: bool,
+    },
     EditingUrl,
-    ConfirmDownload { filename: String },
+    /// Typing a `/` filter for the list on the current tab.
+    Filtering,
+    Finding,
+    Hinting {
+        copy: bool,
+    },
+    NamingBookmark {
+        include_form: bool,
+    },
+    ConfirmDownload {
+        editing_dir: bool,
+    },
+    /// Filling in the add/edit form held in `TuiApp::interface_form`.
+    EditingInterface,
+    Composing,
 }
 
 #[derive(Debug, Clone)]
+/// A download awaiting confirmation. While the modal is open the focused
+/// field's text lives in `TuiApp::input`.
 struct PendingDownload {
     node: NodeInfo,
     path: String,
     filename: String,
+    dir: String,
 }
 
 pub struct TuiApp {
@@ -132,19 +254,25 @@ pub struct TuiApp {
     discovery: DiscoveryView,
     saved: SavedView,
     mynode: MyNodeView,
-    browser: BrowserView,
+    browsers: BrowserTabs,
+    history: HistoryView,
+    conversations: ConversationsView,
+    downloads: DownloadsView,
     interfaces: InterfacesView,
     status_bar: StatusBar,
     input: Input,
     last_edit_popup_area: Rect,
     pending_download: Option<PendingDownload>,
+    interface_form: Option<InterfaceForm>,
     last_download_popup_area: Rect,
+    last_interface_popup_area: Rect,
 
     event_rx: mpsc::Receiver<NetworkEvent>,
     cmd_tx: mpsc::Sender<TuiCommand>,
 
     last_main_area: Rect,
     last_partial_check: std::time::Instant,
+    next_request_id: u64,
 }
 
 fn truncate_filename(name: &str, max_len: usize) -> String {
@@ -155,13 +283,21 @@ fn truncate_filename(name: &str, max_len: usize) -> String {
     format!("{}...", truncated)
 }
 
-fn parse_page_response(data: &[u8]) -> String {
-    if let Ok(response) = rmp_serde::from_slice::<(f64, Vec<u8>, Option<Vec<u8>>)>(data) {
-        if let Some(content) = response.2 {
-            return String::from_utf8_lossy(&content).into_owned();
-        }
+fn download_dir(dir: &str) -> std::path::PathBuf {
+    match dir.trim() {
+        "" => std::path::PathBuf::from(DOWNLOADS_DIR),
+        dir => std::path::PathBuf::from(dir),
     }
-    String::from_utf8_lossy(data).into_owned()
+}
+
+/// Where a download would be written right now, for the modal preview.
+fn download_target(name: &str, dir: &str) -> Result<std::path::PathBuf, TargetError> {
+    let filename = check_filename(name)?;
+    Ok(unique_path(&download_dir(dir), &filename))
+}
+
+fn parse_page_response(data: &[u8]) -> String {
+    PageEnvelope::decode(data).content
 }
 
 fn current_time_secs() -> u64 {
@@ -219,24 +355,47 @@ impl TuiApp {
             discovery,
             saved,
             mynode,
-            browser: BrowserView::new(),
+            browsers: BrowserTabs::new(),
+            history: HistoryView::new(),
+            conversations: ConversationsView::new(),
+            downloads: DownloadsView::new(),
             interfaces,
             status_bar: StatusBar::new(),
             input: Input::default(),
             last_edit_popup_area: Rect::default(),
             pending_download: None,
+            interface_form: None,
             last_download_popup_area: Rect::default(),
+            last_interface_popup_area: Rect::default(),
             event_rx,
             cmd_tx,
             last_main_area: Rect::default(),
             last_partial_check: std::time::Instant::now(),
+            next_request_id: 0,
         })
     }
 
+    pub fn set_history(&mut self, entries: Vec<HistoryEntry>) {
+        self.history.set_entries(entries);
+    }
+
+    pub fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+        self.saved.set_bookmarks(bookmarks);
+    }
+
+    pub fn set_conversations(&mut self, address: [u8; 16], conversations: Vec<Conversation>) {
+        self.conversations.set_conversations(address, conversations);
+    }
+
+    pub fn set_discovered(&mut self, announces: Vec<Announce>) {
+        self.discovery.set_announces(announces);
+    }
+
     pub fn run(&mut self) -> io::Result<()> {
         while self.running {
             self.poll_events();
             self.poll_partials();
+            self.sync_conversation_read();
             self.status_bar.tick();
             self.draw()?;
             self.handle_input()?;
@@ -247,7 +406,7 @@ impl TuiApp {
     fn poll_partials(&mut self) {
         if self.last_partial_check.elapsed() >= Duration::from_secs(1) {
             self.last_partial_check = std::time::Instant::now();
-            if self.browser.browser.has_partials() {
+            if self.browsers.active().browser.has_partials() {
                 self.check_partials();
             }
         }
@@ -256,13 +415,20 @@ impl TuiApp {
     fn poll_events(&mut self) {
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
-                NetworkEvent::NodeAnnounce(node) => {
-                    self.discovery.add_node(node.clone());
+                NetworkEvent::Announce(announce) => {
+                    self.discovery.add(announce.clone());
                     self.mynode.increment_announces_received();
 
-                    if let Some(current) = self.browser.current_node() {
-                        if current.hash == node.hash && current.name != node.name {
-                            self.browser.set_current_node(node.clone());
+                    // Only named node announces carry anything to update.
+                    let Some(node) = announce.node_info().filter(|_| announce.name.is_some())
+                    else {
+                        continue;
+                    };
+                    for view in self.browsers.iter_mut() {
+                        if let Some(current) = view.current_node() {
+                            if current.hash == node.hash && current.name != node.name {
+                                view.set_current_node(node.clone());
+                            }
                         }
                     }
 
@@ -276,29 +442,135 @@ impl TuiApp {
                 NetworkEvent::Status(msg) => {
                     self.status_bar.set_status(msg);
                 }
-                NetworkEvent::PageReceived { url, data } => {
-                    let content = parse_page_response(&data);
-                    self.browser.set_page_content(&url, &content);
-                    self.status_bar.clear_status();
+                NetworkEvent::PageReceived {
+                    request_id,
+                    url,
+                    data,
+                    round_trip_ms,
+                } => {
+                    let Some(idx) = self.browsers.tab_for_request(request_id) else {
+                        log::info!("Discarding stale response for {}", url);
+                        continue;
+                    };
+                    let envelope = PageEnvelope::decode(&data);
+                    let content = envelope.content.clone();
+                    let Some(view) = self.browsers.get_mut(idx) else {
+                        continue;
+                    };
+                    view.set_page_content(&url, &content);
+                    view.record_response(
+                        &url,
+                        envelope,
+                        Some(std::time::Duration::from_millis(round_trip_ms)),
+                    );
+
+                    let node_name = view
+                        .current_node()
+                        .map(|n| n.name.clone())
+                        .unwrap_or_default();
+                    let entry = HistoryEntry::new(url, node_name, &content);
+                    self.history.push(entry.clone());
+                    let _ = self
+                        .cmd_tx
+                        .blocking_send(TuiCommand::RecordHistory { entry });
+
+                    if idx == self.browsers.active_index() {
+                        self.status_bar.clear_status();
+                    }
                 }
-                NetworkEvent::PageFailed { url, reason } => {
-                    self.browser.clear_loading();
-                    self.status_bar
-                        .set_status(format!("Failed to load {}: {}", url, reason));
+                NetworkEvent::PageFailed {
+                    request_id,
+                    url,
+                    reason,
+                } => {
+                    let Some(view) = self
+                        .browsers
+                        .tab_for_request(request_id)
+                        .and_then(|idx| self.browsers.get_mut(idx))
+                    else {
+                        continue;
+                    };
+                    if view.is_showing_stale() {
+                        view.mark_offline();
+                        self.status_bar.set_status(format!(
+                            "Failed to load {}: {} (showing cached copy)",
+                            url, reason
+                        ));
+                    } else {
+                        view.clear_loading();
+                        self.status_bar
+                            .set_status(format!("Failed to load {}: {}", url, reason));
+                    }
                 }
-                NetworkEvent::DownloadComplete { filename, path } => {
-                    self.status_bar.set_status(format!(
-                        "Downloaded {} to {}",
-                        truncate_filename(&filename, 20),
-                        path
-                    ));
+                NetworkEvent::PageCached {
+                    request_id,
+                    url,
+                    data,
+                    fetched_at,
+                } => {
+                    let Some(view) = self
+                        .browsers
+                        .tab_for_request(request_id)
+                        .and_then(|idx| self.browsers.get_mut(idx))
+                    else {
+                        continue;
+                    };
+                    let envelope = PageEnvelope::decode(&data);
+                    view.show_cached(&url, &envelope.content, fetched_at);
+                    view.record_response(&url, envelope, None);
                 }
-                NetworkEvent::DownloadFailed { filename, reason } => {
-                    self.status_bar.set_status(format!(
-                        "Failed to download {}: {}",
-                        truncate_filename(&filename, 20),
-                        reason
-                    ));
+                NetworkEvent::DownloadStarted {
+                    transfer_id,
+                    resumed_from,
+                } => {
+                    self.downloads.mark_started(transfer_id, resumed_from);
+                }
+                NetworkEvent::DownloadProgress {
+                    transfer_id,
+                    received_bytes,
+                    total_bytes,
+                } => {
+                    self.downloads
+                        .update_progress(transfer_id, received_bytes, total_bytes);
+                }
+                NetworkEvent::DownloadComplete { transfer_id, path } => {
+                    if let Some(download) = self.downloads.get(transfer_id) {
+                        self.status_bar.set_status(format!(
+                            "Downloaded {} to {}",
+                            truncate_filename(&download.filename, 20),
+                            path
+                        ));
+                    }
+                    self.downloads.mark_done(transfer_id, path);
+                }
+                NetworkEvent::DownloadFailed {
+                    transfer_id,
+                    reason,
+                } => {
+                    if let Some(download) = self.downloads.get(transfer_id) {
+                        self.status_bar.set_status(format!(
+                            "Failed to download {}: {}",
+                            truncate_filename(&download.filename, 20),
+                            reason
+                        ));
+                    }
+                    self.downloads.mark_failed(transfer_id, reason);
+                }
+                NetworkEvent::MessageReceived { peer, message } => {
+                    if self.tab != Tab::Conversations {
+                        self.status_bar
+                            .set_status(format!("New message from {}", &hex::encode(peer)[..8]));
+                    }
+                    self.conversations.receive(peer, message);
+                }
+                NetworkEvent::MessageSent { peer, id, state } => {
+                    self.conversations.set_state(peer, &id, state);
+                }
+                NetworkEvent::MessageFailed { peer, id, reason } => {
+                    self.status_bar
+                        .set_status(format!("Message not delivered: {}", reason));
+                    self.conversations
+                        .set_state(peer, &id, MessageState::Failed);
                 }
                 NetworkEvent::RelayStats(stats) => {
                     self.mynode.set_stats(stats.clone());
@@ -308,11 +580,7 @@ impl TuiApp {
                     received_bytes,
                     total_bytes,
                 } => {
-                    let pct = if total_bytes > 0 {
-                        (received_bytes * 100) / total_bytes
-                    } else {
-                        0
-                    };
+                    let pct = (received_bytes * 100).checked_div(total_bytes).unwrap_or(0);
                     self.status_bar.set_status(format!(
                         "Downloading... {} / {} ({}%)",
                         super::format_bytes(received_bytes as u64),
@@ -320,21 +588,62 @@ impl TuiApp {
                         pct
                     ));
                 }
-                NetworkEvent::InterfaceStatus { name, connected } => {
-                    self.interfaces.update_status(&name, connected);
+                NetworkEvent::InterfaceStatus {
+                    name,
+                    connected,
+                    retry,
+                } => {
+                    self.interfaces.update_status(&name, connected, retry);
                 }
-                NetworkEvent::PartialReceived { partial, data } => {
-                    let content = parse_page_response(&data);
-                    self.browser.browser.set_partial_content(
-                        &partial,
-                        content,
-                        current_time_secs(),
-                    );
+                NetworkEvent::InterfaceTraffic { name, stats } => {
+                    self.interfaces.update_traffic(&name, stats);
                 }
-                NetworkEvent::PartialFailed { partial, reason } => {
-                    self.browser.browser.set_partial_content(
+                NetworkEvent::Interfaces(interfaces) => {
+                    self.interfaces.set_interfaces(interfaces);
+                }
+                NetworkEvent::InterfaceClients { name, clients } => {
+                    self.interfaces.update_clients(&name, clients);
+                }
+                NetworkEvent::PartialReceived {
+                    request_id,
+                    partial,
+                    data,
+                } => {
+                    if let Some((view, _)) = self.partial_target(request_id) {
+                        let content = parse_page_response(&data);
+                        view.browser
+                            .set_partial_content(&partial, content, current_time_secs());
+                    }
+                }
+                NetworkEvent::PartialFailed {
+                    request_id,
+                    partial,
+                    reason,
+                } => {
+                    if let Some((view, false)) = self.partial_target(request_id) {
+                        view.browser.set_partial_content(
+                            &partial,
+                            format!("`Ff00Error: {}", reason),
+                            current_time_secs(),
+                        );
+                    }
+                }
+                NetworkEvent::PartialCached {
+                    request_id,
+                    partial,
+                    data,
+                } => {
+                    let Some(view) = self
+                        .browsers
+                        .tab_for_partial(request_id)
+                        .and_then(|idx| self.browsers.get_mut(idx))
+                    else {
+                        continue;
+                    };
+                    view.mark_partial_cached(request_id);
+                    view.browser.set_partial_content(
                         &partial,
-                        format!("`Ff00Error: {}", reason),
+                        parse_page_response(&data),
                         current_time_secs(),
                     );
                 }
@@ -348,10 +657,24 @@ impl TuiApp {
         let keybinds = self.keybinds_for_mode();
         let input_value = self.input.value().to_string();
         let input_cursor = self.input.visual_cursor();
+        let page_has_form_data = !self.browsers.active().page_form_data().is_empty();
+        let download_preview = self.download_fields().map(|(name, dir)| {
+            let target = download_target(&name, &dir)
+                .map(|path| path.display().to_string())
+                .map_err(|e| e.to_string());
+            (name, dir, target)
+        });
 
         let mut main_area = Rect::default();
         let mut last_edit_popup_area = Rect::default();
         let mut last_download_popup_area = Rect::default();
+        let interface_preview = self.interface_form_fields().map(|form| {
+            let check = form
+                .to_config(|name| self.interfaces.has_interface(name))
+                .map(|_| ());
+            (form, check)
+        });
+        let mut last_interface_popup_area = Rect::default();
 
         self.terminal.draw(|frame| {
             let area = frame.area();
@@ -419,11 +742,27 @@ impl TuiApp {
             match tab {
                 Tab::Discovery => frame.render_widget(&mut self.discovery, chunks[1]),
                 Tab::Saved => frame.render_widget(&mut self.saved, chunks[1]),
-                Tab::Browser => frame.render_widget(&mut self.browser, chunks[1]),
+                Tab::Browser => frame.render_widget(&mut self.browsers, chunks[1]),
+                Tab::Conversations => frame.render_widget(&mut self.conversations, chunks[1]),
+                Tab::History => frame.render_widget(&mut self.history, chunks[1]),
+                Tab::Downloads => frame.render_widget(&mut self.downloads, chunks[1]),
                 Tab::MyNode => frame.render_widget(&mut self.mynode, chunks[1]),
                 Tab::Interfaces => frame.render_widget(&mut self.interfaces, chunks[1]),
             }
 
+            if mode == AppMode::Composing {
+                let compose = self.conversations.compose_area();
+                let scroll = input_cursor.saturating_sub(compose.width.saturating_sub(1) as usize);
+                let scrolled_value: String = input_value.chars().skip(scroll).collect();
+                Paragraph::new(Span::styled(
+                    scrolled_value,
+                    Style::default().fg(Color::White),
+                ))
+                .render(compose, frame.buffer_mut());
+                let cursor_x = compose.x + (input_cursor - scroll) as u16;
+                frame.set_cursor_position((cursor_x, compose.y));
+            }
+
             if let AppMode::Editing { field_name, masked } = &mode {
                 let inner_width = 50u16.saturating_sub(4);
                 let scroll = input_cursor.saturating_sub(inner_width as usize);
@@ -491,18 +830,78 @@ impl TuiApp {
                 frame.set_cursor_position((cursor_x, inner_y));
             }
 
-            if let AppMode::ConfirmDownload { filename } = &mode {
-                let content = vec![
+            if let AppMode::NamingBookmark { include_form } = &mode {
+                let inner_width = 50u16.saturating_sub(4);
+                let scroll = input_cursor.saturating_sub(inner_width as usize);
+                let scrolled_value: String = input_value.chars().skip(scroll).collect();
+
+                let mut content = vec![
+                    Line::from(Span::styled(
+                        scrolled_value,
+                        Style::default().fg(Color::White),
+                    )),
                     Line::from(""),
+                ];
+                if page_has_form_data {
+                    let checkbox = if *include_form { "[x]" } else { "[ ]" };
+                    content.push(Line::from(Span::styled(
+                        format!("{} Include form data (Tab)", checkbox),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                    content.push(Line::from(""));
+                }
+                let height = content.len() as u16 + 4;
+
+                let modal = Modal::new("Bookmark Title")
+                    .content(content)
+                    .buttons(vec![
+                        ModalButton::new("Cancel", Color::DarkGray),
+                        ModalButton::new("Save", Color::Green),
+                    ])
+                    .border_color(Color::Yellow);
+
+                let popup_area = modal.render_centered(area, frame.buffer_mut(), 50, height);
+                last_edit_popup_area = popup_area;
+
+                let inner_x = popup_area.x + 1;
+                let inner_y = popup_area.y + 1;
+                let cursor_x = inner_x + (input_cursor - scroll) as u16;
+                frame.set_cursor_position((cursor_x, inner_y));
+            }
+
+            if let (AppMode::ConfirmDownload { editing_dir }, Some((name, dir, target))) =
+                (&mode, &download_preview)
+            {
+                let label_width = 10u16;
+                let field_width = 60u16.saturating_sub(2 + label_width + 1);
+                let scroll = input_cursor.saturating_sub(field_width as usize);
+                let field = |label: &'static str, value: &str, focused: bool| {
+                    let (value, color) = if focused {
+                        (value.chars().skip(scroll).collect(), Color::White)
+                    } else {
+                        (value.to_string(), Color::Gray)
+                    };
                     Line::from(vec![
-                        Span::styled("  File: ", Style::default().fg(Color::DarkGray)),
-                        Span::styled(filename.clone(), Style::default().fg(Color::White)),
+                        Span::styled(label, Style::default().fg(Color::DarkGray)),
+                        Span::styled(value, Style::default().fg(color)),
+                    ])
+                };
+                let target_line = match target {
+                    Ok(path) => Line::from(vec![
+                        Span::styled("  Saves as ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(path.clone(), Style::default().fg(Color::Cyan)),
                     ]),
-                    Line::from(""),
-                    Line::from(Span::styled(
-                        "  Download this file?",
-                        Style::default().fg(Color::DarkGray),
+                    Err(e) => Line::from(Span::styled(
+                        format!("  {}", e),
+                        Style::default().fg(Color::Red),
                     )),
+                };
+                let content = vec![
+                    Line::from(""),
+                    field("  Name    ", name, !editing_dir),
+                    field("  Folder  ", dir, *editing_dir),
+                    Line::from(""),
+                    target_line,
                     Line::from(""),
                 ];
 
@@ -514,7 +913,72 @@ impl TuiApp {
                     ])
                     .border_color(Color::Yellow);
 
-                last_download_popup_area = modal.render_centered(area, frame.buffer_mut(), 50, 9);
+                last_download_popup_area = modal.render_centered(area, frame.buffer_mut(), 60, 10);
+
+                let field_y = last_download_popup_area.y + if *editing_dir { 3 } else { 2 };
+                let cursor_x =
+                    last_download_popup_area.x + 1 + label_width + (input_cursor - scroll) as u16;
+                frame.set_cursor_position((cursor_x, field_y));
+            }
+
+            if let Some((form, check)) = &interface_preview {
+                let label_width = 12u16;
+                let field_width = 60u16.saturating_sub(2 + label_width + 1);
+                let scroll = input_cursor.saturating_sub(field_width as usize);
+                let field = |field: FormField| {
+                    let value = form.field(field);
+                    let (value, color) = if field == form.focus {
+                        (value.chars().skip(scroll).collect(), Color::White)
+                    } else {
+                        (value.to_string(), Color::Gray)
+                    };
+                    Line::from(vec![
+                        Span::styled(form.label(field), Style::default().fg(Color::DarkGray)),
+                        Span::styled(value, Style::default().fg(color)),
+                    ])
+                };
+                let check_line = match check {
+                    Ok(()) if form.enabled => Line::from(Span::styled(
+                        "  Applies immediately",
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                    Ok(()) => Line::from(Span::styled(
+                        "  Saved disabled; press t to enable it",
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                    Err(e) => Line::from(Span::styled(
+                        format!("  {}", e),
+                        Style::default().fg(Color::Red),
+                    )),
+                };
+                let content = vec![
+                    Line::from(""),
+                    field(FormField::Name),
+                    field(FormField::Host),
+                    field(FormField::Port),
+                    Line::from(""),
+                    check_line,
+                    Line::from(""),
+                ];
+
+                let modal = Modal::new(form.title())
+                    .content(content)
+                    .buttons(vec![
+                        ModalButton::new("Cancel", Color::DarkGray),
+                        ModalButton::new("Save", Color::Green),
+                    ])
+                    .border_color(Color::Yellow);
+
+                last_interface_popup_area = modal.render_centered(area, frame.buffer_mut(), 60, 11);
+
+                let row = match form.focus {
+                    FormField::Name => 2,
+                    FormField::Host => 3,
+                    FormField::Port => 4,
+                };
+                let cursor_x =
+                    last_interface_popup_area.x + 1 + label_width + (input_cursor - scroll) as u16;
+                frame.set_cursor_position((cursor_x, last_interface_popup_area.y + row));
             }
 
             let footer =
@@ -525,16 +989,41 @@ impl TuiApp {
         self.last_main_area = main_area;
         self.last_edit_popup_area = last_edit_popup_area;
         self.last_download_popup_area = last_download_popup_area;
+        self.last_interface_popup_area = last_interface_popup_area;
 
         Ok(())
     }
 
     fn keybinds_for_mode(&self) -> Line<'static> {
         match &self.mode {
+            AppMode::EditingInterface => Line::from(vec![
+                Span::styled(" [Enter]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Save  "),
+                Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Next Field  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Cancel  "),
+            ]),
             AppMode::ConfirmDownload { .. } => Line::from(vec![
-                Span::styled(" [Enter/y]", Style::default().fg(Color::Magenta)),
+                Span::styled(" [Enter]", Style::default().fg(Color::Magenta)),
                 Span::raw(" Download  "),
-                Span::styled("[Esc/n]", Style::default().fg(Color::Magenta)),
+                Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Name/Folder  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Cancel  "),
+            ]),
+            AppMode::NamingBookmark { .. } => Line::from(vec![
+                Span::styled(" [Enter]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Save  "),
+                Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Form Data  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Cancel  "),
+            ]),
+            AppMode::Composing => Line::from(vec![
+                Span::styled(" [Enter]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Send  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
                 Span::raw(" Cancel  "),
             ]),
             AppMode::Editing { .. } | AppMode::EditingUrl => Line::from(vec![
@@ -543,6 +1032,36 @@ impl TuiApp {
                 Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
                 Span::raw(" Cancel  "),
             ]),
+            AppMode::Hinting { copy: true } => Line::from(vec![
+                Span::styled(" [a-z]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Copy URL  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Cancel  "),
+            ]),
+            AppMode::Hinting { copy: false } => Line::from(vec![
+                Span::styled(" [a-z]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Follow  "),
+                Span::styled("[A-Z]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Background Tab  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Cancel  "),
+            ]),
+            AppMode::Finding => Line::from(vec![
+                Span::styled(" [Enter]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Done  "),
+                Span::styled("[Up/Down]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Prev/Next  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Clear  "),
+            ]),
+            AppMode::Filtering => Line::from(vec![
+                Span::styled(" [Enter]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Done  "),
+                Span::styled("[Up/Down]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Navigate  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Magenta)),
+                Span::raw(" Clear  "),
+            ]),
             AppMode::Normal => match self.tab {
                 Tab::Discovery => {
                     if self.discovery.is_modal_open() {
@@ -560,6 +1079,12 @@ impl TuiApp {
                             Span::raw(" Navigate  "),
                             Span::styled("[Enter]", Style::default().fg(Color::Magenta)),
                             Span::raw(" Open  "),
+                            Span::styled("[/]", Style::default().fg(Color::Magenta)),
+                            Span::raw(" Filter  "),
+                            Span::styled("[f]", Style::default().fg(Color::Magenta)),
+                            Span::raw(" Type  "),
+                            Span::styled("[s]", Style::default().fg(Color::Magenta)),
+                            Span::raw(" Sort  "),
                             Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
                             Span::raw(" Switch Tab  "),
                             Span::styled("[q]", Style::default().fg(Color::Magenta)),
@@ -574,11 +1099,65 @@ impl TuiApp {
                     Span::raw(" Connect  "),
                     Span::styled("[d]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Remove  "),
+                    Span::styled("[/]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Filter  "),
+                    Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Switch Tab  "),
+                    Span::styled("[q]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Quit  "),
+                ]),
+                Tab::Conversations => Line::from(vec![
+                    Span::styled(" [j/k]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Navigate  "),
+                    Span::styled("[c]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Compose  "),
+                    Span::styled("[n]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" New  "),
+                    Span::styled("[PgUp/PgDn]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Scroll  "),
+                    Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Switch Tab  "),
+                    Span::styled("[q]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Quit  "),
+                ]),
+                Tab::History => Line::from(vec![
+                    Span::styled(" [j/k]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Navigate  "),
+                    Span::styled("[Enter]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Open  "),
+                    Span::styled("[/]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Filter  "),
+                    Span::styled("[n]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" This Node  "),
+                    Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Switch Tab  "),
+                    Span::styled("[q]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Quit  "),
+                ]),
+                Tab::Downloads => Line::from(vec![
+                    Span::styled(" [j/k]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Navigate  "),
+                    Span::styled("[x/X]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Cancel/Discard  "),
+                    Span::styled("[r]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Retry  "),
+                    Span::styled("[o]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Open Folder  "),
+                    Span::styled("[d]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Remove  "),
                     Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Switch Tab  "),
                     Span::styled("[q]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Quit  "),
                 ]),
+                Tab::Browser if self.browsers.active().is_loading() => Line::from(vec![
+                    Span::styled(" [Esc]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Stop  "),
+                    Span::styled("[j/k]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Scroll  "),
+                    Span::styled("[q]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Quit  "),
+                ]),
                 Tab::Browser => Line::from(vec![
                     Span::styled(" [j/k]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Scroll  "),
@@ -590,6 +1169,18 @@ impl TuiApp {
                     Span::raw(" Save  "),
                     Span::styled("[r]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Reload  "),
+                    Span::styled("[b]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Bookmark  "),
+                    Span::styled("[/]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Find  "),
+                    Span::styled("[f/y]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Hints/Copy  "),
+                    Span::styled("[v/i]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Source/Inspect  "),
+                    Span::styled("[t/w]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" New/Close Tab  "),
+                    Span::styled("[[/]]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Switch Tab  "),
                     Span::styled("[q]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Quit  "),
                 ]),
@@ -606,6 +1197,14 @@ impl TuiApp {
                     Span::raw(" Navigate  "),
                     Span::styled("[r]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Reconnect  "),
+                    Span::styled("[a/A]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Add Client/Server  "),
+                    Span::styled("[e]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Edit  "),
+                    Span::styled("[t]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Enable/Disable  "),
+                    Span::styled("[d]", Style::default().fg(Color::Magenta)),
+                    Span::raw(" Delete  "),
                     Span::styled("[Tab]", Style::default().fg(Color::Magenta)),
                     Span::raw(" Switch Tab  "),
                     Span::styled("[q]", Style::default().fg(Color::Magenta)),
@@ -638,7 +1237,13 @@ impl TuiApp {
                 match &self.mode {
                     AppMode::Editing { .. } => self.handle_editing_key(&evt),
                     AppMode::EditingUrl => self.handle_url_editing_key(&evt),
-                    AppMode::ConfirmDownload { .. } => self.handle_download_key(key.code),
+                    AppMode::Filtering => self.handle_filter_key(&evt),
+                    AppMode::Finding => self.handle_find_key(&evt),
+                    AppMode::Hinting { copy } => self.handle_hint_key(key.code, *copy),
+                    AppMode::NamingBookmark { .. } => self.handle_bookmark_key(&evt),
+                    AppMode::ConfirmDownload { .. } => self.handle_download_key(&evt),
+                    AppMode::EditingInterface => self.handle_interface_form_key(&evt),
+                    AppMode::Composing => self.handle_compose_key(&evt),
                     AppMode::Normal => self.handle_normal_key(key.code, ctrl),
                 }
             } else if let Event::Mouse(mouse) = &evt {
@@ -667,25 +1272,58 @@ impl TuiApp {
         if self.tab == Tab::Browser {
             match code {
                 KeyCode::Char('q') => self.running = false,
-                KeyCode::Down | KeyCode::Char('j') => self.browser.scroll_down(),
-                KeyCode::Up | KeyCode::Char('k') => self.browser.scroll_up(),
-                KeyCode::PageDown => self.browser.scroll_page_down(),
-                KeyCode::PageUp => self.browser.scroll_page_up(),
-                KeyCode::Tab => self.browser.select_next(),
-                KeyCode::BackTab => self.browser.select_prev(),
-                KeyCode::Left => self.browser.select_prev(),
-                KeyCode::Right => self.browser.select_next(),
+                KeyCode::Down | KeyCode::Char('j') => self.browsers.active_mut().scroll_down(),
+                KeyCode::Up | KeyCode::Char('k') => self.browsers.active_mut().scroll_up(),
+                KeyCode::PageDown => self.browsers.active_mut().scroll_page_down(),
+                KeyCode::PageUp => self.browsers.active_mut().scroll_page_up(),
+                KeyCode::Tab => self.browsers.active_mut().select_next(),
+                KeyCode::BackTab => self.browsers.active_mut().select_prev(),
+                KeyCode::Left => self.browsers.active_mut().select_prev(),
+                KeyCode::Right => self.browsers.active_mut().select_next(),
                 KeyCode::Enter => {
-                    if let Some(interaction) = self.browser.interact() {
+                    if let Some(interaction) = self.browsers.active_mut().interact() {
                         self.handle_interaction(interaction);
                     }
                 }
+                KeyCode::Esc => {
+                    if self.browsers.active().is_loading() {
+                        self.stop_loading();
+                    } else {
+                        self.browsers.active_mut().clear_find();
+                    }
+                }
+                KeyCode::Char('/') => {
+                    let query = self.browsers.active().find_query().to_string();
+                    self.input = Input::new(query);
+                    self.mode = AppMode::Finding;
+                }
+                // While a search is active n/N step through its matches;
+                // otherwise n opens the selected link in a new tab.
+                KeyCode::Char('n') if self.browsers.active().is_finding() => {
+                    self.browsers.active_mut().find_next()
+                }
+                KeyCode::Char('N') if self.browsers.active().is_finding() => {
+                    self.browsers.active_mut().find_prev()
+                }
                 KeyCode::Backspace => {
-                    self.browser.go_back();
+                    self.stop_loading();
+                    if self.browsers.active_mut().go_back() {
+                        self.revalidate_current_page();
+                    }
                 }
                 KeyCode::Char('r') => {
                     self.reload_page();
                 }
+                KeyCode::Char('t') => self.new_browser_tab(),
+                KeyCode::Char('w') => self.close_browser_tab(self.browsers.active_index()),
+                KeyCode::Char(']') => self.browsers.select_next(),
+                KeyCode::Char('[') => self.browsers.select_prev(),
+                KeyCode::Char('n') => self.open_selected_in_new_tab(),
+                KeyCode::Char('b') => self.begin_bookmark(),
+                KeyCode::Char('v') => self.browsers.active_mut().toggle_source(),
+                KeyCode::Char('f') => self.begin_hints(false),
+                KeyCode::Char('y') => self.begin_hints(true),
+                KeyCode::Char('i') => self.browsers.active_mut().toggle_inspector(),
                 KeyCode::F(12) => {
                     self.debug_save_page();
                 }
@@ -707,15 +1345,64 @@ impl TuiApp {
             KeyCode::Down | KeyCode::Char('j') => self.handle_down(),
             KeyCode::Up | KeyCode::Char('k') => self.handle_up(),
             KeyCode::Enter => self.handle_enter(),
+            KeyCode::Char('a') if self.tab == Tab::Interfaces => {
+                self.begin_interface_form(InterfaceForm::new(InterfaceKind::TcpClient))
+            }
+            KeyCode::Char('A') if self.tab == Tab::Interfaces => {
+                self.begin_interface_form(InterfaceForm::new(InterfaceKind::TcpServer))
+            }
+            KeyCode::Char('e') if self.tab == Tab::Interfaces => {
+                if let Some(iface) = self.interfaces.selected_interface() {
+                    self.begin_interface_form(InterfaceForm::edit(iface));
+                }
+            }
+            KeyCode::Char('t') if self.tab == Tab::Interfaces => self.toggle_interface(),
             KeyCode::Char('a') => self.handle_announce(),
             KeyCode::Char('d') => self.handle_delete(),
             KeyCode::Char('r') if self.tab == Tab::Interfaces => self.handle_reconnect(),
-            KeyCode::Char('s') if self.tab == Tab::Browser => self.save_current_browser_node(),
-            _ => {}
-        }
-    }
-
-    fn handle_editing_key(&mut self, evt: &Event) {
+            KeyCode::Char('x') if self.tab == Tab::Downloads => {
+                self.handle_download_action(DownloadAction::Cancel)
+            }
+            KeyCode::Char('X') if self.tab == Tab::Downloads => {
+                if let Some((transfer_id, DownloadAction::Cancel)) =
+                    self.downloads.selected_action()
+                {
+                    self.stop_download(transfer_id, true);
+                }
+            }
+            KeyCode::Char('r') if self.tab == Tab::Downloads => {
+                self.handle_download_action(DownloadAction::Retry)
+            }
+            KeyCode::Char('o') if self.tab == Tab::Downloads => {
+                self.handle_download_action(DownloadAction::OpenFolder)
+            }
+            KeyCode::Char('f') if self.tab == Tab::Discovery => self.discovery.cycle_kind_filter(),
+            KeyCode::Char('s') if self.tab == Tab::Discovery => self.discovery.cycle_sort(),
+            KeyCode::Char('/')
+                if matches!(self.tab, Tab::Discovery | Tab::Saved | Tab::History) =>
+            {
+                self.begin_filter()
+            }
+            KeyCode::Esc if self.tab == Tab::Discovery => self.discovery.set_filter(""),
+            KeyCode::Esc if self.tab == Tab::Saved => self.saved.set_filter(""),
+            KeyCode::Char('n') if self.tab == Tab::History => self.history.toggle_node_filter(),
+            KeyCode::Char('c') if self.tab == Tab::Conversations => self.begin_compose(),
+            KeyCode::Char('n') if self.tab == Tab::Conversations => {
+                self.input = Input::new("nomad@".to_string());
+                self.mode = AppMode::EditingUrl;
+            }
+            KeyCode::PageUp if self.tab == Tab::Conversations => {
+                self.conversations.scroll_thread_up(10)
+            }
+            KeyCode::PageDown if self.tab == Tab::Conversations => {
+                self.conversations.scroll_thread_down(10)
+            }
+            KeyCode::Char('s') if self.tab == Tab::Browser => self.save_current_browser_node(),
+            _ => {}
+        }
+    }
+
+    fn handle_editing_key(&mut self, evt: &Event) {
         if let Event::Key(key) = evt {
             match key.code {
                 KeyCode::Enter => {
@@ -735,7 +1422,7 @@ impl TuiApp {
         if let AppMode::Editing { field_name, .. } = &self.mode {
             let value = self.input.value().to_string();
             let name = field_name.clone();
-            self.browser.set_field_value(&name, value);
+            self.browsers.active_mut().set_field_value(&name, value);
         }
         self.input.reset();
         self.mode = AppMode::Normal;
@@ -746,6 +1433,62 @@ impl TuiApp {
         self.mode = AppMode::Normal;
     }
 
+    fn begin_compose(&mut self) {
+        if self.conversations.selected_peer().is_none() {
+            self.status_bar
+                .set_status("Open a conversation first (n or a nomad@ link)".into());
+            return;
+        }
+        self.input.reset();
+        self.mode = AppMode::Composing;
+    }
+
+    fn handle_compose_key(&mut self, evt: &Event) {
+        if let Event::Key(key) = evt {
+            match key.code {
+                KeyCode::Enter => self.send_message(),
+                KeyCode::Esc => {
+                    self.input.reset();
+                    self.mode = AppMode::Normal;
+                }
+                _ => {
+                    self.input.handle_event(evt);
+                }
+            }
+        }
+    }
+
+    fn send_message(&mut self) {
+        let content = self.input.value().trim().to_string();
+        self.input.reset();
+        self.mode = AppMode::Normal;
+        let Some(peer) = self.conversations.selected_peer() else {
+            return;
+        };
+        if content.is_empty() {
+            return;
+        }
+
+        let message = DirectMessage::new(peer, self.conversations.address(), "", &content);
+        self.conversations
+            .push_outgoing(peer, StoredMessage::outgoing(&message));
+        let _ = self
+            .cmd_tx
+            .blocking_send(TuiCommand::SendMessage { message });
+    }
+
+    /// Marks the conversation on screen as read.
+    fn sync_conversation_read(&mut self) {
+        if self.tab != Tab::Conversations {
+            return;
+        }
+        if let Some(peer) = self.conversations.take_unread() {
+            let _ = self
+                .cmd_tx
+                .blocking_send(TuiCommand::MarkConversationRead { peer });
+        }
+    }
+
     fn handle_url_editing_key(&mut self, evt: &Event) {
         if let Event::Key(key) = evt {
             match key.code {
@@ -758,6 +1501,239 @@ impl TuiApp {
         }
     }
 
+    fn begin_filter(&mut self) {
+        let current = match self.tab {
+            Tab::Discovery => self.discovery.filter(),
+            Tab::Saved => self.saved.filter(),
+            Tab::History => self.history.filter(),
+            _ => return,
+        };
+        self.input = Input::new(current.to_string());
+        self.mode = AppMode::Filtering;
+    }
+
+    fn handle_filter_key(&mut self, evt: &Event) {
+        if let Event::Key(key) = evt {
+            match key.code {
+                KeyCode::Enter => self.finish_filter(),
+                KeyCode::Esc => {
+                    self.set_tab_filter("");
+                    self.finish_filter();
+                }
+                KeyCode::Down if self.tab == Tab::History => self.history.select_next(),
+                KeyCode::Up if self.tab == Tab::History => self.history.select_prev(),
+                KeyCode::Down => self.handle_down(),
+                KeyCode::Up => self.handle_up(),
+                _ => {
+                    self.input.handle_event(evt);
+                    let value = self.input.value().to_string();
+                    self.set_tab_filter(&value);
+                }
+            }
+        }
+    }
+
+    fn set_tab_filter(&mut self, filter: &str) {
+        match self.tab {
+            Tab::Discovery => self.discovery.set_filter(filter),
+            Tab::Saved => self.saved.set_filter(filter),
+            Tab::History => self.history.set_filter(filter),
+            _ => {}
+        }
+    }
+
+    fn handle_find_key(&mut self, evt: &Event) {
+        if let Event::Key(key) = evt {
+            match key.code {
+                KeyCode::Enter => {
+                    if self.input.value().is_empty() {
+                        self.browsers.active_mut().clear_find();
+                    }
+                    self.finish_find();
+                }
+                KeyCode::Esc => {
+                    self.browsers.active_mut().clear_find();
+                    self.finish_find();
+                }
+                KeyCode::Down => self.browsers.active_mut().find_next(),
+                KeyCode::Up => self.browsers.active_mut().find_prev(),
+                _ => {
+                    self.input.handle_event(evt);
+                    let query = self.input.value().to_string();
+                    self.browsers.active_mut().set_find_query(&query);
+                }
+            }
+        }
+    }
+
+    fn finish_find(&mut self) {
+        self.input.reset();
+        self.mode = AppMode::Normal;
+    }
+
+    fn begin_hints(&mut self, copy: bool) {
+        let view = self.browsers.active_mut();
+        if view.is_showing_source() {
+            self.status_bar
+                .set_status("Link hints are not available in source view".into());
+            return;
+        }
+        if view.show_hints() == 0 {
+            self.status_bar.set_status("No links on screen".into());
+            return;
+        }
+        self.mode = AppMode::Hinting { copy };
+    }
+
+    fn handle_hint_key(&mut self, code: KeyCode, copy: bool) {
+        let view = self.browsers.active_mut();
+        let Some(hints) = view.hints_mut() else {
+            self.mode = AppMode::Normal;
+            return;
+        };
+        match code {
+            KeyCode::Esc => {
+                view.take_hints();
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Backspace => hints.pop(),
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => match hints.push(c) {
+                HintOutcome::Pending => {}
+                HintOutcome::NoMatch => {
+                    view.take_hints();
+                    self.mode = AppMode::Normal;
+                    self.status_bar.set_status("No matching hint".into());
+                }
+                HintOutcome::Selected => {
+                    let interaction = view.take_hints().and_then(LinkHints::take_selected);
+                    self.mode = AppMode::Normal;
+                    if let Some(interaction) = interaction {
+                        self.activate_hint(interaction, copy, c.is_ascii_uppercase());
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Shift+label opens links in a background tab; in copy mode the link's
+    /// full URL goes to the clipboard instead.
+    fn activate_hint(&mut self, interaction: micronaut::Interaction, copy: bool, background: bool) {
+        match interaction {
+            micronaut::Interaction::Link(link) if copy => {
+                use super::link_handler::LinkAction;
+                let url = match self
+                    .browsers
+                    .active()
+                    .resolve_link(&link, &self.known_nodes())
+                {
+                    LinkAction::Navigate { node, path } => format!("{}:{}", node.hash_hex(), path),
+                    _ => link.url,
+                };
+                self.copy_to_clipboard(&url);
+            }
+            _ if copy => self.status_bar.set_status("Not a link".into()),
+            micronaut::Interaction::Link(link) if background => {
+                self.open_link_in_new_tab(link, false)
+            }
+            interaction => self.handle_interaction(interaction),
+        }
+    }
+
+    fn begin_bookmark(&mut self) {
+        let view = self.browsers.active();
+        let Some(url) = view.current_url() else {
+            self.status_bar.set_status("No page to bookmark".into());
+            return;
+        };
+        let title = view
+            .browser
+            .content
+            .as_deref()
+            .and_then(page_title)
+            .unwrap_or_else(|| url.split_once(':').map_or(url, |(_, p)| p).to_string());
+
+        self.input = Input::new(title);
+        self.mode = AppMode::NamingBookmark {
+            include_form: false,
+        };
+    }
+
+    fn handle_bookmark_key(&mut self, evt: &Event) {
+        if let Event::Key(key) = evt {
+            match key.code {
+                KeyCode::Enter => self.confirm_bookmark(),
+                KeyCode::Esc => self.cancel_url_edit(),
+                KeyCode::Tab => {
+                    if let AppMode::NamingBookmark { include_form } = &mut self.mode {
+                        *include_form = !*include_form;
+                    }
+                }
+                _ => {
+                    self.input.handle_event(evt);
+                }
+            }
+        }
+    }
+
+    fn handle_bookmark_modal_click(&mut self, x: u16, y: u16) {
+        let modal = Modal::new("").buttons(vec![
+            ModalButton::new("Cancel", Color::DarkGray),
+            ModalButton::new("Save", Color::Green),
+        ]);
+
+        if let Some(idx) = modal.hit_test_buttons(x, y, self.last_edit_popup_area) {
+            match idx {
+                0 => self.cancel_url_edit(),
+                1 => self.confirm_bookmark(),
+                _ => {}
+            }
+        }
+    }
+
+    fn confirm_bookmark(&mut self) {
+        let include_form = matches!(self.mode, AppMode::NamingBookmark { include_form: true });
+        let title = self.input.value().trim().to_string();
+        self.input.reset();
+        self.mode = AppMode::Normal;
+
+        let view = self.browsers.active();
+        let (Some(node), Some(url)) = (view.current_node().cloned(), view.current_url()) else {
+            return;
+        };
+        let bookmark = Bookmark {
+            url: url.to_string(),
+            title: if title.is_empty() {
+                url.to_string()
+            } else {
+                title
+            },
+            form_data: if include_form {
+                view.page_form_data()
+            } else {
+                std::collections::HashMap::new()
+            },
+        };
+
+        // Bookmarks are listed under their node, so keep the node around too.
+        if !self.saved.nodes().iter().any(|n| n.hash == node.hash) {
+            self.saved.add_node(node.clone());
+            let _ = self.cmd_tx.blocking_send(TuiCommand::SaveNode { node });
+        }
+
+        self.status_bar
+            .set_status(format!("Bookmarked {}", bookmark.title));
+        self.saved.save_bookmark(bookmark.clone());
+        let _ = self
+            .cmd_tx
+            .blocking_send(TuiCommand::SaveBookmark { bookmark });
+    }
+
+    fn finish_filter(&mut self) {
+        self.input.reset();
+        self.mode = AppMode::Normal;
+    }
+
     fn confirm_url_edit(&mut self) {
         let url = self.input.value().to_string();
         self.input.reset();
@@ -813,18 +1789,56 @@ impl TuiApp {
         }
     }
 
-    fn handle_download_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
-                self.confirm_download();
-            }
-            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
-                self.cancel_download();
+    fn handle_download_key(&mut self, evt: &Event) {
+        if let Event::Key(key) = evt {
+            match key.code {
+                KeyCode::Enter => self.confirm_download(),
+                KeyCode::Esc => self.cancel_download(),
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    self.switch_download_field()
+                }
+                _ => {
+                    self.input.handle_event(evt);
+                }
             }
-            _ => {}
         }
     }
 
+    /// The name and folder as currently shown in the download modal.
+    fn download_fields(&self) -> Option<(String, String)> {
+        let AppMode::ConfirmDownload { editing_dir } = self.mode else {
+            return None;
+        };
+        let pending = self.pending_download.as_ref()?;
+        let typed = self.input.value().to_string();
+        Some(if editing_dir {
+            (pending.filename.clone(), typed)
+        } else {
+            (typed, pending.dir.clone())
+        })
+    }
+
+    fn switch_download_field(&mut self) {
+        let AppMode::ConfirmDownload { editing_dir } = self.mode else {
+            return;
+        };
+        let Some(pending) = self.pending_download.as_mut() else {
+            return;
+        };
+        let typed = self.input.value().to_string();
+        let next = if editing_dir {
+            pending.dir = typed;
+            pending.filename.clone()
+        } else {
+            pending.filename = typed;
+            pending.dir.clone()
+        };
+        self.input = Input::new(next);
+        self.mode = AppMode::ConfirmDownload {
+            editing_dir: !editing_dir,
+        };
+    }
+
     fn handle_download_modal_click(&mut self, x: u16, y: u16) {
         let area = self.last_download_popup_area;
 
@@ -833,6 +1847,13 @@ impl TuiApp {
             return;
         }
 
+        if let AppMode::ConfirmDownload { editing_dir } = self.mode {
+            if (y == area.y + 2 && editing_dir) || (y == area.y + 3 && !editing_dir) {
+                self.switch_download_field();
+                return;
+            }
+        }
+
         let modal = Modal::new("")
             .buttons(vec![
                 ModalButton::new("Cancel", Color::DarkGray),
@@ -850,25 +1871,176 @@ impl TuiApp {
     }
 
     fn confirm_download(&mut self) {
+        let Some((name, dir)) = self.download_fields() else {
+            self.cancel_download();
+            return;
+        };
+        let filename = match check_filename(&name) {
+            Ok(filename) => filename,
+            Err(e) => {
+                self.status_bar.set_status(format!("Can't download: {}", e));
+                return;
+            }
+        };
+        let dir = download_dir(&dir);
+
         if let Some(download) = self.pending_download.take() {
             self.status_bar.set_status(format!(
-                "Downloading {}...",
-                truncate_filename(&download.filename, 20)
+                "Queued {} (see Downloads)",
+                truncate_filename(&filename, 20)
             ));
+            let request_id = self.next_request_id();
+            self.downloads.add(
+                request_id,
+                download.node.clone(),
+                download.path.clone(),
+                filename.clone(),
+                dir.clone(),
+            );
             let _ = self.cmd_tx.blocking_send(TuiCommand::DownloadFile {
+                request_id,
                 node: download.node,
                 path: download.path,
-                filename: download.filename,
+                filename,
+                dir,
             });
         }
+        self.input.reset();
         self.mode = AppMode::Normal;
     }
 
     fn cancel_download(&mut self) {
         self.pending_download = None;
+        self.input.reset();
+        self.mode = AppMode::Normal;
+    }
+
+    fn begin_interface_form(&mut self, form: InterfaceForm) {
+        self.input = Input::new(form.field(form.focus).to_string());
+        self.interface_form = Some(form);
+        self.mode = AppMode::EditingInterface;
+    }
+
+    fn handle_interface_form_key(&mut self, evt: &Event) {
+        if let Event::Key(key) = evt {
+            match key.code {
+                KeyCode::Enter => self.save_interface_form(),
+                KeyCode::Esc => self.cancel_interface_form(),
+                KeyCode::Tab | KeyCode::Down => self.focus_interface_field(None),
+                KeyCode::BackTab | KeyCode::Up => {
+                    if let Some(form) = &self.interface_form {
+                        self.focus_interface_field(Some(form.focus.next().next()));
+                    }
+                }
+                _ => {
+                    self.input.handle_event(evt);
+                }
+            }
+        }
+    }
+
+    /// The form as currently shown, including what's typed in the focused field.
+    fn interface_form_fields(&self) -> Option<InterfaceForm> {
+        if self.mode != AppMode::EditingInterface {
+            return None;
+        }
+        let mut form = self.interface_form.clone()?;
+        form.set_field(form.focus, self.input.value().to_string());
+        Some(form)
+    }
+
+    /// Moves focus to `field`, or the next one for `None`.
+    fn focus_interface_field(&mut self, field: Option<FormField>) {
+        let Some(form) = self.interface_form.as_mut() else {
+            return;
+        };
+        form.set_field(form.focus, self.input.value().to_string());
+        form.focus = field.unwrap_or(form.focus.next());
+        self.input = Input::new(form.field(form.focus).to_string());
+    }
+
+    fn handle_interface_modal_click(&mut self, x: u16, y: u16) {
+        let area = self.last_interface_popup_area;
+
+        if !area.contains((x, y).into()) {
+            self.cancel_interface_form();
+            return;
+        }
+
+        let field = match y.checked_sub(area.y) {
+            Some(2) => Some(FormField::Name),
+            Some(3) => Some(FormField::Host),
+            Some(4) => Some(FormField::Port),
+            _ => None,
+        };
+        if field.is_some() {
+            self.focus_interface_field(field);
+            return;
+        }
+
+        let modal = Modal::new("")
+            .buttons(vec![
+                ModalButton::new("Cancel", Color::DarkGray),
+                ModalButton::new("Save", Color::Green),
+            ])
+            .selected(1);
+
+        match modal.hit_test_buttons(x, y, area) {
+            Some(0) => self.cancel_interface_form(),
+            Some(1) => self.save_interface_form(),
+            _ => {}
+        }
+    }
+
+    fn save_interface_form(&mut self) {
+        let Some(form) = self.interface_form_fields() else {
+            self.cancel_interface_form();
+            return;
+        };
+        match form.to_config(|name| self.interfaces.has_interface(name)) {
+            Ok((name, config)) => {
+                self.status_bar.set_status(format!("Saving {}...", name));
+                let _ = self.cmd_tx.blocking_send(TuiCommand::SaveInterface {
+                    original: form.original,
+                    name,
+                    config,
+                });
+                self.cancel_interface_form();
+            }
+            Err(e) => self.status_bar.set_status(e),
+        }
+    }
+
+    fn cancel_interface_form(&mut self) {
+        self.interface_form = None;
+        self.input.reset();
         self.mode = AppMode::Normal;
     }
 
+    fn toggle_interface(&mut self) {
+        let Some(iface) = self.interfaces.selected_interface() else {
+            return;
+        };
+        let mut form = InterfaceForm::edit(iface);
+        form.enabled = !form.enabled;
+        match form.to_config(|_| false) {
+            Ok((name, config)) => {
+                let verb = if form.enabled {
+                    "Enabling"
+                } else {
+                    "Disabling"
+                };
+                self.status_bar.set_status(format!("{} {}...", verb, name));
+                let _ = self.cmd_tx.blocking_send(TuiCommand::SaveInterface {
+                    original: form.original,
+                    name,
+                    config,
+                });
+            }
+            Err(e) => self.status_bar.set_status(e),
+        }
+    }
+
     fn handle_interaction(&mut self, interaction: micronaut::Interaction) {
         match interaction {
             micronaut::Interaction::Link(link) => {
@@ -888,17 +2060,28 @@ impl TuiApp {
     }
 
     fn refresh_partials_by_id(&mut self, partial_ids: &[String]) {
-        let Some(node) = self.browser.current_node().cloned() else {
+        let Some(node) = self.browsers.active_mut().current_node().cloned() else {
             return;
         };
 
         let now_secs = current_time_secs();
-        let all_partials = self.browser.browser.partials_needing_update(now_secs);
+        let all_partials = self
+            .browsers
+            .active_mut()
+            .browser
+            .partials_needing_update(now_secs);
 
         for partial in all_partials {
             if partial_ids.iter().any(|id| partial.url.contains(id)) {
-                let form_data = self.browser.browser.partial_form_data(&partial);
+                let form_data = self
+                    .browsers
+                    .active_mut()
+                    .browser
+                    .partial_form_data(&partial);
+                let request_id = self.next_request_id();
+                self.browsers.active_mut().track_partial(request_id);
                 let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPartial {
+                    request_id,
                     node: node.clone(),
                     partial,
                     form_data,
@@ -908,16 +2091,27 @@ impl TuiApp {
     }
 
     fn check_partials(&mut self) {
-        let Some(node) = self.browser.current_node().cloned() else {
+        let Some(node) = self.browsers.active_mut().current_node().cloned() else {
             return;
         };
 
         let now_secs = current_time_secs();
-        let partials = self.browser.browser.partials_needing_update(now_secs);
+        let partials = self
+            .browsers
+            .active_mut()
+            .browser
+            .partials_needing_update(now_secs);
 
         for partial in partials {
-            let form_data = self.browser.browser.partial_form_data(&partial);
+            let form_data = self
+                .browsers
+                .active_mut()
+                .browser
+                .partial_form_data(&partial);
+            let request_id = self.next_request_id();
+            self.browsers.active_mut().track_partial(request_id);
             let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPartial {
+                request_id,
                 node: node.clone(),
                 partial,
                 form_data,
@@ -926,25 +2120,174 @@ impl TuiApp {
     }
 
     fn reload_page(&mut self) {
-        if let Some(node) = self.browser.current_node().cloned() {
-            if let Some(url) = self.browser.current_url() {
+        if let Some(node) = self.browsers.active_mut().current_node().cloned() {
+            if let Some(url) = self.browsers.active_mut().current_url() {
                 let path = if let Some(idx) = url.find(':') {
                     url[idx + 1..].to_string()
                 } else {
                     url.to_string()
                 };
-                self.browser.set_loading(path.clone());
-                let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPage {
-                    node,
-                    path,
-                    form_data: std::collections::HashMap::new(),
-                });
+                self.fetch_page(node, path, std::collections::HashMap::new());
             }
         }
     }
 
+    fn next_request_id(&mut self) -> u64 {
+        self.next_request_id += 1;
+        self.next_request_id
+    }
+
+    fn fetch_page(
+        &mut self,
+        node: NodeInfo,
+        path: String,
+        form_data: std::collections::HashMap<String, String>,
+    ) {
+        self.fetch_page_in(self.browsers.active_index(), node, path, form_data);
+    }
+
+    fn fetch_page_in(
+        &mut self,
+        tab: usize,
+        node: NodeInfo,
+        path: String,
+        form_data: std::collections::HashMap<String, String>,
+    ) {
+        let request_id = self.next_request_id();
+        let Some(view) = self.browsers.get_mut(tab) else {
+            return;
+        };
+        let superseded = view.take_pending_request();
+        view.set_loading(path.clone(), request_id, form_data.clone());
+
+        if let Some(old) = superseded {
+            let _ = self
+                .cmd_tx
+                .blocking_send(TuiCommand::CancelRequest { request_id: old });
+        }
+        let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPage {
+            request_id,
+            node,
+            path,
+            form_data,
+        });
+    }
+
+    fn stop_loading(&mut self) {
+        if let Some(request_id) = self.browsers.active_mut().take_pending_request() {
+            let _ = self
+                .cmd_tx
+                .blocking_send(TuiCommand::CancelRequest { request_id });
+            self.status_bar.set_status("Stopped".into());
+        }
+    }
+
+    /// The tab a partial response belongs to, and whether it is already
+    /// showing a cached copy of that partial.
+    fn partial_target(&mut self, request_id: u64) -> Option<(&mut BrowserView, bool)> {
+        let idx = self.browsers.tab_for_partial(request_id)?;
+        let view = self.browsers.get_mut(idx)?;
+        let cached = view.finish_partial(request_id);
+        Some((view, cached))
+    }
+
+    /// Refetches the page on screen after back/forward so it is refreshed
+    /// in place, the way a cached page is.
+    fn revalidate_current_page(&mut self) {
+        let view = self.browsers.active();
+        let Some(node) = view.current_node().cloned() else {
+            return;
+        };
+        let Some((_, path)) = view.current_url().and_then(split_page_url) else {
+            return;
+        };
+        let path = path.to_string();
+        let form_data = view.page_form_data();
+
+        let request_id = self.next_request_id();
+        self.browsers
+            .active_mut()
+            .set_revalidating(request_id, form_data.clone());
+        let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPage {
+            request_id,
+            node,
+            path,
+            form_data,
+        });
+    }
+
+    fn new_browser_tab(&mut self) {
+        self.browsers.open(BrowserView::new(), true);
+    }
+
+    fn close_browser_tab(&mut self, idx: usize) {
+        if let Some(mut view) = self.browsers.close(idx) {
+            let pending = view
+                .take_pending_request()
+                .into_iter()
+                .chain(view.take_pending_partials());
+            for request_id in pending {
+                let _ = self
+                    .cmd_tx
+                    .blocking_send(TuiCommand::CancelRequest { request_id });
+            }
+        }
+    }
+
+    fn handle_tab_strip_action(&mut self, action: TabStripAction) {
+        match action {
+            TabStripAction::Select(idx) => self.browsers.select(idx),
+            TabStripAction::Close(idx) => self.close_browser_tab(idx),
+            TabStripAction::New => self.new_browser_tab(),
+        }
+    }
+
+    fn open_selected_in_new_tab(&mut self) {
+        match self.browsers.active_mut().interact() {
+            Some(micronaut::Interaction::Link(link)) => self.open_link_in_new_tab(link, true),
+            Some(interaction) => self.handle_interaction(interaction),
+            None => {}
+        }
+    }
+
+    fn open_link_in_new_tab(&mut self, link: micronaut::Link, focus: bool) {
+        let all_nodes = self.known_nodes();
+
+        use super::link_handler::LinkAction;
+        match self.browsers.active().resolve_link(&link, &all_nodes) {
+            LinkAction::Navigate { node, path } => {
+                let mut view = BrowserView::new();
+                view.set_identify_enabled(self.identify_enabled_for(&node));
+                view.set_current_node(node.clone());
+                let idx = self.browsers.open(view, focus);
+                self.fetch_page_in(idx, node, path, link.form_data);
+                if !focus {
+                    self.status_bar.set_status("Opened in new tab".into());
+                }
+            }
+            _ => self.navigate_to_link(link),
+        }
+    }
+
+    fn known_nodes(&self) -> Vec<NodeInfo> {
+        self.discovery
+            .nodes()
+            .into_iter()
+            .chain(self.saved.nodes().iter().cloned())
+            .collect()
+    }
+
+    fn identify_enabled_for(&self, node: &NodeInfo) -> bool {
+        self.saved
+            .nodes()
+            .iter()
+            .find(|n| n.hash == node.hash)
+            .map(|n| n.identify)
+            .unwrap_or(false)
+    }
+
     fn save_current_browser_node(&mut self) {
-        if let Some(node) = self.browser.current_node().cloned() {
+        if let Some(node) = self.browsers.active_mut().current_node().cloned() {
             let already_saved = self.saved.nodes().iter().any(|n| n.hash == node.hash);
             if !already_saved {
                 self.saved.add_node(node.clone());
@@ -963,7 +2306,7 @@ impl TuiApp {
     }
 
     fn toggle_browser_node_identify(&mut self) {
-        if let Some(node) = self.browser.current_node().cloned() {
+        if let Some(node) = self.browsers.active_mut().current_node().cloned() {
             let hash = node.hash;
             let currently_enabled = self
                 .saved
@@ -992,7 +2335,11 @@ impl TuiApp {
             // Update local state
             let new_state = !currently_enabled;
             self.saved.set_identify(hash, new_state);
-            self.browser.set_identify_enabled(new_state);
+            for view in self.browsers.iter_mut() {
+                if view.current_node().is_some_and(|n| n.hash == hash) {
+                    view.set_identify_enabled(new_state);
+                }
+            }
 
             if new_state {
                 self.status_bar.set_status("Self-identify enabled".into());
@@ -1016,6 +2363,17 @@ impl TuiApp {
                     }
                 }
 
+                if self.mode == AppMode::Filtering {
+                    self.finish_filter();
+                }
+                if self.mode == AppMode::Finding {
+                    self.finish_find();
+                }
+                if let AppMode::Hinting { .. } = self.mode {
+                    self.browsers.active_mut().take_hints();
+                    self.mode = AppMode::Normal;
+                }
+
                 match &self.mode {
                     AppMode::Editing { .. } => {
                         self.handle_edit_modal_click(x, y);
@@ -1026,6 +2384,19 @@ impl TuiApp {
                     AppMode::ConfirmDownload { .. } => {
                         self.handle_download_modal_click(x, y);
                     }
+                    AppMode::EditingInterface => {
+                        self.handle_interface_modal_click(x, y);
+                    }
+                    AppMode::Filtering | AppMode::Finding | AppMode::Hinting { .. } => {}
+                    AppMode::Composing => {
+                        if self.conversations.click(x, y) != ConversationClick::Compose {
+                            self.input.reset();
+                            self.mode = AppMode::Normal;
+                        }
+                    }
+                    AppMode::NamingBookmark { .. } => {
+                        self.handle_bookmark_modal_click(x, y);
+                    }
                     AppMode::Normal => {
                         if self.discovery.is_modal_open() {
                             let modal_action =
@@ -1047,19 +2418,44 @@ impl TuiApp {
                                 let action = self.saved.click_detail(x, y);
                                 self.handle_saved_modal_action(action);
                             }
+                            Tab::Conversations => {
+                                if self.conversations.click(x, y) == ConversationClick::Compose {
+                                    self.begin_compose();
+                                }
+                            }
+                            Tab::History => {
+                                self.history.click(x, y);
+                                match self.history.click_detail(x, y) {
+                                    HistoryAction::Open => self.open_history_entry(),
+                                    HistoryAction::FilterNode => self.history.toggle_node_filter(),
+                                    HistoryAction::None => {}
+                                }
+                            }
                             Tab::Browser => {
                                 use super::browser_view::NavAction;
-                                if let Some(nav) = self.browser.click_nav(x, y) {
+                                if let Some(action) = self.browsers.click_strip(x, y) {
+                                    self.handle_tab_strip_action(action);
+                                } else if let Some(nav) = self.browsers.active_mut().click_nav(x, y)
+                                {
                                     match nav {
                                         NavAction::Back => {
-                                            self.browser.go_back();
+                                            self.stop_loading();
+                                            if self.browsers.active_mut().go_back() {
+                                                self.revalidate_current_page();
+                                            }
                                         }
                                         NavAction::Forward => {
-                                            self.browser.go_forward();
+                                            self.stop_loading();
+                                            if self.browsers.active_mut().go_forward() {
+                                                self.revalidate_current_page();
+                                            }
                                         }
                                         NavAction::Reload => {
                                             self.reload_page();
                                         }
+                                        NavAction::Stop => {
+                                            self.stop_loading();
+                                        }
                                         NavAction::ToggleIdentify => {
                                             self.toggle_browser_node_identify();
                                         }
@@ -1067,15 +2463,26 @@ impl TuiApp {
                                             self.save_current_browser_node();
                                         }
                                     }
-                                } else if self.browser.click_url_bar(x, y) {
-                                    let current =
-                                        self.browser.current_url().unwrap_or("").to_string();
+                                } else if self.browsers.active_mut().click_url_bar(x, y) {
+                                    let current = self
+                                        .browsers
+                                        .active_mut()
+                                        .current_url()
+                                        .unwrap_or("")
+                                        .to_string();
                                     self.input = Input::new(current);
                                     self.mode = AppMode::EditingUrl;
-                                } else if let Some(interaction) = self.browser.click(x, y) {
+                                } else if let Some(interaction) =
+                                    self.browsers.active_mut().click(x, y)
+                                {
                                     self.handle_interaction(interaction);
                                 }
                             }
+                            Tab::Downloads => {
+                                if let Some((_, action)) = self.downloads.click(x, y) {
+                                    self.handle_download_action(action);
+                                }
+                            }
                             Tab::MyNode => {
                                 if self.mynode.click(x, y) {
                                     self.send_announce();
@@ -1093,17 +2500,44 @@ impl TuiApp {
                     }
                 }
             }
+            MouseEventKind::Down(MouseButton::Middle)
+                if self.tab == Tab::Browser && matches!(self.mode, AppMode::Normal) =>
+            {
+                if let Some(idx) = self.browsers.hit_test_tab(x, y) {
+                    self.close_browser_tab(idx);
+                } else if let Some(micronaut::Interaction::Link(link)) =
+                    self.browsers.active_mut().click(x, y)
+                {
+                    self.open_link_in_new_tab(link, false);
+                }
+            }
             MouseEventKind::ScrollUp => match &self.mode {
-                AppMode::Normal if self.tab == Tab::Browser => self.browser.scroll_up(),
-                AppMode::Normal => self.handle_up(),
-                AppMode::Editing { .. } | AppMode::EditingUrl | AppMode::ConfirmDownload { .. } => {
+                AppMode::Normal | AppMode::Finding if self.tab == Tab::Browser => {
+                    self.browsers.active_mut().scroll_up()
                 }
+                AppMode::Normal | AppMode::Filtering => self.handle_up(),
+                AppMode::Editing { .. }
+                | AppMode::EditingUrl
+                | AppMode::Finding
+                | AppMode::Hinting { .. }
+                | AppMode::NamingBookmark { .. }
+                | AppMode::ConfirmDownload { .. }
+                | AppMode::EditingInterface
+                | AppMode::Composing => {}
             },
             MouseEventKind::ScrollDown => match &self.mode {
-                AppMode::Normal if self.tab == Tab::Browser => self.browser.scroll_down(),
-                AppMode::Normal => self.handle_down(),
-                AppMode::Editing { .. } | AppMode::EditingUrl | AppMode::ConfirmDownload { .. } => {
+                AppMode::Normal | AppMode::Finding if self.tab == Tab::Browser => {
+                    self.browsers.active_mut().scroll_down()
                 }
+                AppMode::Normal | AppMode::Filtering => self.handle_down(),
+                AppMode::Editing { .. }
+                | AppMode::EditingUrl
+                | AppMode::Finding
+                | AppMode::Hinting { .. }
+                | AppMode::NamingBookmark { .. }
+                | AppMode::ConfirmDownload { .. }
+                | AppMode::EditingInterface
+                | AppMode::Composing => {}
             },
             _ => {}
         }
@@ -1113,6 +2547,9 @@ impl TuiApp {
         match self.tab {
             Tab::Discovery => self.discovery.scroll_down(),
             Tab::Saved => self.saved.scroll_down(),
+            Tab::Conversations => self.conversations.scroll_down(),
+            Tab::History => self.history.scroll_down(),
+            Tab::Downloads => self.downloads.scroll_down(),
             Tab::Interfaces => self.interfaces.scroll_down(),
             Tab::Browser | Tab::MyNode => {}
         }
@@ -1122,6 +2559,9 @@ impl TuiApp {
         match self.tab {
             Tab::Discovery => self.discovery.scroll_up(),
             Tab::Saved => self.saved.scroll_up(),
+            Tab::Conversations => self.conversations.scroll_up(),
+            Tab::History => self.history.scroll_up(),
+            Tab::Downloads => self.downloads.scroll_up(),
             Tab::Interfaces => self.interfaces.scroll_up(),
             Tab::Browser | Tab::MyNode => {}
         }
@@ -1138,6 +2578,13 @@ impl TuiApp {
                 }
             }
             Tab::Browser => {}
+            Tab::Conversations => self.begin_compose(),
+            Tab::History => self.open_history_entry(),
+            Tab::Downloads => {
+                if let Some((_, action)) = self.downloads.selected_action() {
+                    self.handle_download_action(action);
+                }
+            }
             Tab::MyNode => {
                 self.send_announce();
             }
@@ -1150,13 +2597,13 @@ impl TuiApp {
     fn handle_modal_action(&mut self, action: ModalAction) {
         match action {
             ModalAction::Connect => {
-                if let Some(node) = self.discovery.selected_node().cloned() {
+                if let Some(node) = self.discovery.selected_node() {
                     self.discovery.close_modal();
                     self.connect_to_node(&node);
                 }
             }
             ModalAction::Save => {
-                if let Some(node) = self.discovery.selected_node().cloned() {
+                if let Some(node) = self.discovery.selected_node() {
                     self.saved.add_node(node.clone());
                     let _ = self.cmd_tx.blocking_send(TuiCommand::SaveNode { node });
                     self.discovery.close_modal();
@@ -1164,8 +2611,16 @@ impl TuiApp {
                 }
             }
             ModalAction::Copy => {
-                if let Some(node) = self.discovery.selected_node() {
-                    self.copy_to_clipboard(&node.hash_hex());
+                if let Some(announce) = self.discovery.selected_announce() {
+                    self.copy_to_clipboard(&announce.hash_hex());
+                }
+            }
+            ModalAction::Message => {
+                if let Some(announce) = self.discovery.selected_announce() {
+                    self.conversations.open(announce.hash);
+                    self.discovery.close_modal();
+                    self.tab = Tab::Conversations;
+                    self.tab_bar = TabBar::new(self.tab);
                 }
             }
             ModalAction::Dismiss => {
@@ -1178,12 +2633,20 @@ impl TuiApp {
     fn handle_saved_modal_action(&mut self, action: SavedModalAction) {
         match action {
             SavedModalAction::Connect => {
-                if let Some(node) = self.saved.selected_node().cloned() {
+                if let Some(bookmark) = self.saved.selected_bookmark().cloned() {
+                    self.open_bookmark(bookmark);
+                } else if let Some(node) = self.saved.selected_node().cloned() {
                     self.connect_to_node(&node);
                 }
             }
             SavedModalAction::Delete => {
-                if let Some(removed) = self.saved.remove_selected() {
+                if let Some(removed) = self.saved.remove_selected_bookmark() {
+                    self.status_bar
+                        .set_status(format!("Removed {}", removed.title));
+                    let _ = self
+                        .cmd_tx
+                        .blocking_send(TuiCommand::RemoveBookmark { url: removed.url });
+                } else if let Some(removed) = self.saved.remove_selected() {
                     let _ = self
                         .cmd_tx
                         .blocking_send(TuiCommand::RemoveNode { hash: removed.hash });
@@ -1192,7 +2655,10 @@ impl TuiApp {
                 }
             }
             SavedModalAction::Copy => {
-                if let Some(node) = self.saved.selected_node() {
+                if let Some(bookmark) = self.saved.selected_bookmark() {
+                    let url = bookmark.url.clone();
+                    self.copy_to_clipboard(&url);
+                } else if let Some(node) = self.saved.selected_node() {
                     self.copy_to_clipboard(&node.hash_hex());
                 }
             }
@@ -1217,8 +2683,50 @@ impl TuiApp {
     }
 
     fn connect_to_node(&mut self, node: &NodeInfo) {
-        let path = "/page/index.mu".to_string();
-        self.browser.set_current_node(node.clone());
+        self.open_page(
+            node,
+            "/page/index.mu".to_string(),
+            std::collections::HashMap::new(),
+        );
+    }
+
+    fn open_bookmark(&mut self, bookmark: Bookmark) {
+        let Some(hash) = bookmark.node_hash() else {
+            self.status_bar.set_status("Invalid bookmark URL".into());
+            return;
+        };
+        let node = self
+            .known_nodes()
+            .into_iter()
+            .find(|n| n.hash == hash)
+            .unwrap_or_else(|| NodeInfo {
+                name: format!("<{}>", &hex::encode(hash)[..8]),
+                hash,
+                identify: false,
+            });
+        let path = bookmark.path().to_string();
+        self.open_page(&node, path, bookmark.form_data);
+    }
+
+    fn open_history_entry(&mut self) {
+        let Some((node, path)) = self.history.selected_entry().and_then(|e| e.target()) else {
+            return;
+        };
+        let node = self
+            .known_nodes()
+            .into_iter()
+            .find(|n| n.hash == node.hash)
+            .unwrap_or(node);
+        self.open_page(&node, path, std::collections::HashMap::new());
+    }
+
+    fn open_page(
+        &mut self,
+        node: &NodeInfo,
+        path: String,
+        form_data: std::collections::HashMap<String, String>,
+    ) {
+        self.browsers.active_mut().set_current_node(node.clone());
 
         let identify_enabled = self
             .saved
@@ -1227,69 +2735,55 @@ impl TuiApp {
             .find(|n| n.hash == node.hash)
             .map(|n| n.identify)
             .unwrap_or(false);
-        self.browser.set_identify_enabled(identify_enabled);
+        self.browsers
+            .active_mut()
+            .set_identify_enabled(identify_enabled);
 
-        self.browser.set_loading(path.clone());
         self.tab = Tab::Browser;
         self.tab_bar = TabBar::new(Tab::Browser);
 
-        let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPage {
-            node: node.clone(),
-            path,
-            form_data: std::collections::HashMap::new(),
-        });
+        self.fetch_page(node.clone(), path, form_data);
 
         self.status_bar
             .set_status(format!("Connecting to {}...", node.name));
     }
 
     fn navigate_to_link(&mut self, link: micronaut::Link) {
-        let all_nodes: Vec<NodeInfo> = self
-            .discovery
-            .nodes()
-            .iter()
-            .chain(self.saved.nodes().iter())
-            .cloned()
-            .collect();
+        let all_nodes = self.known_nodes();
 
         use super::link_handler::LinkAction;
-        match self.browser.resolve_link(&link, &all_nodes) {
+        match self.browsers.active().resolve_link(&link, &all_nodes) {
             LinkAction::Navigate { node, path } => {
-                self.browser.set_current_node(node.clone());
-
-                let identify_enabled = self
-                    .saved
-                    .nodes()
-                    .iter()
-                    .find(|n| n.hash == node.hash)
-                    .map(|n| n.identify)
-                    .unwrap_or(false);
-                self.browser.set_identify_enabled(identify_enabled);
-
-                self.browser.set_loading(path.clone());
-                let _ = self.cmd_tx.blocking_send(TuiCommand::FetchPage {
-                    node,
-                    path,
-                    form_data: link.form_data,
-                });
+                let identify_enabled = self.identify_enabled_for(&node);
+                let view = self.browsers.active_mut();
+                view.set_current_node(node.clone());
+                view.set_identify_enabled(identify_enabled);
+
+                self.fetch_page(node, path, link.form_data);
             }
             LinkAction::Download {
                 node,
                 path,
                 filename,
             } => {
+                self.input = Input::new(filename.clone());
                 self.pending_download = Some(PendingDownload {
                     node,
                     path,
-                    filename: filename.clone(),
+                    filename,
+                    dir: DOWNLOADS_DIR.to_string(),
                 });
-                self.mode = AppMode::ConfirmDownload { filename };
+                self.mode = AppMode::ConfirmDownload { editing_dir: false };
+            }
+            LinkAction::Message { hash } => {
+                self.conversations.open(hash);
+                self.tab = Tab::Conversations;
+                self.tab_bar = TabBar::new(self.tab);
             }
-            LinkAction::Lxmf { hash } => {
-                self.status_bar.set_status(format!(
-                    "LXMF links not yet supported: {}",
-                    hex::encode(hash)
-                ));
+            LinkAction::Lxmf => {
+                self.status_bar.set_status(
+                    "LXMF isn't supported, only nomad@ addresses can be messaged".into(),
+                );
             }
             LinkAction::Unknown { url } => {
                 self.status_bar
@@ -1310,8 +2804,71 @@ impl TuiApp {
     }
 
     fn handle_delete(&mut self) {
-        if self.tab == Tab::Saved {
-            self.handle_saved_modal_action(SavedModalAction::Delete);
+        match self.tab {
+            Tab::Saved => self.handle_saved_modal_action(SavedModalAction::Delete),
+            Tab::Downloads => {
+                if let Some(removed) = self.downloads.remove_selected() {
+                    self.status_bar
+                        .set_status(format!("Removed {}", removed.filename));
+                }
+            }
+            Tab::Interfaces => {
+                if let Some(iface) = self.interfaces.selected_interface() {
+                    let name = iface.name.clone();
+                    self.status_bar.set_status(format!("Removing {}...", name));
+                    let _ = self
+                        .cmd_tx
+                        .blocking_send(TuiCommand::DeleteInterface { name });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn stop_download(&mut self, transfer_id: u64, discard: bool) {
+        self.downloads.mark_cancelled(transfer_id);
+        let _ = self.cmd_tx.blocking_send(TuiCommand::CancelDownload {
+            transfer_id,
+            discard,
+        });
+    }
+
+    /// Applies `action` to the selected download if its state allows it.
+    fn handle_download_action(&mut self, action: DownloadAction) {
+        let Some((transfer_id, allowed)) = self.downloads.selected_action() else {
+            return;
+        };
+        if action != allowed {
+            return;
+        }
+        match action {
+            DownloadAction::Cancel => self.stop_download(transfer_id, false),
+            DownloadAction::Retry => {
+                if let Some(download) = self.downloads.retry(transfer_id) {
+                    let _ = self.cmd_tx.blocking_send(TuiCommand::DownloadFile {
+                        request_id: transfer_id,
+                        node: download.node.clone(),
+                        path: download.path.clone(),
+                        filename: download.filename.clone(),
+                        dir: download.dir.clone(),
+                    });
+                }
+            }
+            DownloadAction::OpenFolder => {
+                let Some(DownloadState::Done { path }) =
+                    self.downloads.selected().map(|d| d.state.clone())
+                else {
+                    return;
+                };
+                let dir = std::path::Path::new(&path)
+                    .parent()
+                    .unwrap_or(std::path::Path::new("."))
+                    .to_path_buf();
+                if let Err(e) = open_folder(&dir) {
+                    self.status_bar
+                        .set_status(format!("Failed to open {}: {}", dir.display(), e));
+                }
+            }
         }
     }
 
@@ -1335,12 +2892,12 @@ impl TuiApp {
     }
 
     fn debug_save_page(&mut self) {
-        let Some(content) = self.browser.browser.content.as_ref() else {
+        let Some(content) = self.browsers.active().browser.content.as_ref() else {
             self.status_bar.set_status("No page content to save".into());
             return;
         };
 
-        let filename = if let Some(url) = self.browser.browser.url.as_ref() {
+        let filename = if let Some(url) = self.browsers.active().browser.url.as_ref() {
             let safe_name: String = url
                 .chars()
                 .map(|c: char| {
@@ -0,0 +1,219 @@
+use super::browser_view::BrowserView;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+const MAX_TITLE_LEN: usize = 18;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabStripAction {
+    Select(usize),
+    Close(usize),
+    New,
+}
+
+pub struct BrowserTabs {
+    tabs: Vec<BrowserView>,
+    active: usize,
+    last_tab_areas: Vec<(usize, Rect)>,
+    last_close_areas: Vec<(usize, Rect)>,
+    last_new_tab_area: Rect,
+}
+
+impl Default for BrowserTabs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BrowserTabs {
+    pub fn new() -> Self {
+        Self {
+            tabs: vec![BrowserView::new()],
+            active: 0,
+            last_tab_areas: Vec::new(),
+            last_close_areas: Vec::new(),
+            last_new_tab_area: Rect::default(),
+        }
+    }
+
+    pub fn active(&self) -> &BrowserView {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut BrowserView {
+        &mut self.tabs[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut BrowserView> {
+        self.tabs.get_mut(idx)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut BrowserView> {
+        self.tabs.iter_mut()
+    }
+
+    /// Inserts `view` right after the active tab and returns its index.
+    pub fn open(&mut self, view: BrowserView, focus: bool) -> usize {
+        let idx = self.active + 1;
+        self.tabs.insert(idx, view);
+        if focus {
+            self.active = idx;
+        }
+        idx
+    }
+
+    /// Removes a tab. The last remaining tab is replaced by an empty one so
+    /// there is always something to render.
+    pub fn close(&mut self, idx: usize) -> Option<BrowserView> {
+        if idx >= self.tabs.len() {
+            return None;
+        }
+        let removed = if self.tabs.len() == 1 {
+            std::mem::take(&mut self.tabs[0])
+        } else {
+            self.tabs.remove(idx)
+        };
+        if self.active > idx || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        Some(removed)
+    }
+
+    pub fn select(&mut self, idx: usize) {
+        if idx < self.tabs.len() {
+            self.active = idx;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.active = (self.active + 1) % self.tabs.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.active = self.active.checked_sub(1).unwrap_or(self.tabs.len() - 1);
+    }
+
+    pub fn tab_for_request(&self, request_id: u64) -> Option<usize> {
+        self.tabs.iter().position(|t| t.is_pending(request_id))
+    }
+
+    pub fn tab_for_partial(&self, request_id: u64) -> Option<usize> {
+        self.tabs
+            .iter()
+            .position(|t| t.is_partial_pending(request_id))
+    }
+
+    pub fn click_strip(&self, x: u16, y: u16) -> Option<TabStripAction> {
+        let point = Rect::new(x, y, 1, 1);
+        if let Some((idx, _)) = self
+            .last_close_areas
+            .iter()
+            .find(|(_, area)| area.intersects(point))
+        {
+            return Some(TabStripAction::Close(*idx));
+        }
+        if let Some((idx, _)) = self
+            .last_tab_areas
+            .iter()
+            .find(|(_, area)| area.intersects(point))
+        {
+            return Some(TabStripAction::Select(*idx));
+        }
+        if self.last_new_tab_area.intersects(point) {
+            return Some(TabStripAction::New);
+        }
+        None
+    }
+
+    /// Which tab was under the cursor, for middle-click handling.
+    pub fn hit_test_tab(&self, x: u16, y: u16) -> Option<usize> {
+        let point = Rect::new(x, y, 1, 1);
+        self.last_tab_areas
+            .iter()
+            .chain(self.last_close_areas.iter())
+            .find(|(_, area)| area.intersects(point))
+            .map(|(idx, _)| *idx)
+    }
+
+    fn render_strip(&mut self, area: Rect, buf: &mut Buffer) {
+        self.last_tab_areas.clear();
+        self.last_close_areas.clear();
+
+        let right = area.x + area.width;
+        let mut x = area.x;
+
+        for (idx, tab) in self.tabs.iter().enumerate() {
+            let is_active = idx == self.active;
+            let mut title = tab.title();
+            if title.chars().count() > MAX_TITLE_LEN {
+                title = title.chars().take(MAX_TITLE_LEN - 1).collect();
+                title.push('\u{2026}');
+            }
+            let marker = if tab.is_loading() { "\u{2026}" } else { "" };
+            let label = format!(" {}{} ", title, marker);
+            let label_width = label.chars().count() as u16;
+            let close_label = "\u{00d7} ";
+
+            if x + label_width + 2 + 4 > right {
+                buf.set_string(x, area.y, "\u{2026}", Style::default().fg(Color::DarkGray));
+                x += 2;
+                break;
+            }
+
+            let style = if is_active {
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray).bg(Color::Rgb(30, 30, 40))
+            };
+            buf.set_string(x, area.y, &label, style);
+            self.last_tab_areas
+                .push((idx, Rect::new(x, area.y, label_width, 1)));
+            x += label_width;
+
+            let close_style = if is_active {
+                style.remove_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .bg(Color::Rgb(30, 30, 40))
+            };
+            buf.set_string(x, area.y, close_label, close_style);
+            self.last_close_areas
+                .push((idx, Rect::new(x, area.y, 1, 1)));
+            x += 3;
+        }
+
+        let new_tab = " + ";
+        if x + new_tab.len() as u16 <= right {
+            buf.set_string(x, area.y, new_tab, Style::default().fg(Color::Cyan));
+            self.last_new_tab_area = Rect::new(x, area.y, new_tab.len() as u16, 1);
+        } else {
+            self.last_new_tab_area = Rect::default();
+        }
+    }
+}
+
+impl Widget for &mut BrowserTabs {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 2 {
+            return;
+        }
+        let strip_area = Rect::new(area.x, area.y, area.width, 1);
+        let view_area = Rect::new(area.x, area.y + 1, area.width, area.height - 1);
+
+        self.render_strip(strip_area, buf);
+        let active = self.active;
+        (&mut self.tabs[active]).render(view_area, buf);
+    }
+}
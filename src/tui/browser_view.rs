@@ -1,3 +1,6 @@
+use super::find::{capture_page, capture_viewport, find_matches, viewport_matches, FindState};
+use super::link_hints::{interaction_key, LinkHints};
+use super::page_source::{highlight_micron, PageEnvelope};
 use crate::network::NodeInfo;
 use micronaut::{Browser, Interaction, Link, RatatuiRenderer};
 use ratatui::{
@@ -5,23 +8,46 @@ use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
 };
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NavAction {
     Back,
     Forward,
     Reload,
+    Stop,
     ToggleIdentify,
     Save,
 }
 
+/// What came back for a page, kept for the inspector pane.
+struct PageResponse {
+    envelope: PageEnvelope,
+    round_trip: Option<Duration>,
+}
+
 pub struct BrowserView {
     pub browser: Browser<RatatuiRenderer>,
     current_node: Option<NodeInfo>,
     loading_url: Option<String>,
+    loading_form_data: HashMap<String, String>,
+    form_data_by_url: HashMap<String, HashMap<String, String>>,
+    responses_by_url: HashMap<String, PageResponse>,
+    pending_request: Option<u64>,
+    pending_partials: HashMap<u64, bool>,
+    cached_at: Option<i64>,
+    showing_stale: bool,
+    offline: bool,
     identify_enabled: bool,
+    find: Option<FindState>,
+    show_source: bool,
+    source_scroll: usize,
+    source_cache: Option<(String, Vec<Line<'static>>)>,
+    inspector_open: bool,
+    hints: Option<LinkHints>,
     last_content_area: Rect,
     last_back_btn_area: Rect,
     last_fwd_btn_area: Rect,
@@ -43,7 +69,21 @@ impl BrowserView {
             browser: Browser::new(RatatuiRenderer),
             current_node: None,
             loading_url: None,
+            loading_form_data: HashMap::new(),
+            form_data_by_url: HashMap::new(),
+            responses_by_url: HashMap::new(),
+            pending_request: None,
+            pending_partials: HashMap::new(),
+            cached_at: None,
+            showing_stale: false,
+            offline: false,
             identify_enabled: false,
+            find: None,
+            show_source: false,
+            source_scroll: 0,
+            source_cache: None,
+            inspector_open: false,
+            hints: None,
             last_content_area: Rect::default(),
             last_back_btn_area: Rect::default(),
             last_fwd_btn_area: Rect::default(),
@@ -71,34 +111,499 @@ impl BrowserView {
     }
 
     pub fn set_page_content(&mut self, url: &str, content: &str) {
+        self.remember_form_data(url);
+        self.clear_loading();
+        self.cached_at = None;
+        self.showing_stale = false;
+        self.offline = false;
+        self.source_scroll = 0;
+        self.browser.set_content(url, content);
+    }
+
+    /// Shows a cached copy while the request for it is still in flight.
+    pub fn show_cached(&mut self, url: &str, content: &str, fetched_at: i64) {
+        self.remember_form_data(url);
         self.loading_url = None;
+        self.cached_at = Some(fetched_at);
+        self.showing_stale = true;
+        self.offline = false;
+        self.source_scroll = 0;
         self.browser.set_content(url, content);
     }
 
-    pub fn set_loading(&mut self, url: String) {
+    /// Refetches the page already on screen, leaving it visible meanwhile.
+    pub fn set_revalidating(&mut self, request_id: u64, form_data: HashMap<String, String>) {
+        self.loading_form_data = form_data;
+        self.pending_request = Some(request_id);
+        self.showing_stale = true;
+        self.offline = false;
+    }
+
+    /// Whether the page on screen is an older copy awaiting a fresh one.
+    pub fn is_showing_stale(&self) -> bool {
+        self.showing_stale
+    }
+
+    /// The refetch failed, keep the stale page and flag it.
+    pub fn mark_offline(&mut self) {
+        self.clear_loading();
+        self.showing_stale = false;
+        self.offline = true;
+    }
+
+    /// Keeps the decoded response for the inspector. `round_trip` is `None`
+    /// for copies served from the cache.
+    pub fn record_response(
+        &mut self,
+        url: &str,
+        envelope: PageEnvelope,
+        round_trip: Option<Duration>,
+    ) {
+        self.responses_by_url.insert(
+            url.to_string(),
+            PageResponse {
+                envelope,
+                round_trip,
+            },
+        );
+    }
+
+    pub fn toggle_source(&mut self) {
+        self.show_source = !self.show_source;
+        self.source_scroll = 0;
+        self.find = None;
+    }
+
+    pub fn is_showing_source(&self) -> bool {
+        self.show_source
+    }
+
+    pub fn toggle_inspector(&mut self) {
+        self.inspector_open = !self.inspector_open;
+    }
+
+    /// Labels every link and field in the viewport, returning how many were
+    /// found. Cells are probed with `Browser::interaction_at`, the same hit
+    /// test a click uses but without selecting anything.
+    pub fn show_hints(&mut self) -> usize {
+        let area = self.last_content_area;
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        for y in 0..area.height {
+            for x in 0..area.width {
+                if let Some(interaction) = self.browser.interaction_at(x, y) {
+                    if seen.insert(interaction_key(&interaction)) {
+                        found.push((x, y, interaction));
+                    }
+                }
+            }
+        }
+        let count = found.len();
+        self.hints = (count > 0).then(|| LinkHints::new(found));
+        count
+    }
+
+    pub fn hints_mut(&mut self) -> Option<&mut LinkHints> {
+        self.hints.as_mut()
+    }
+
+    pub fn take_hints(&mut self) -> Option<LinkHints> {
+        self.hints.take()
+    }
+
+    /// The micron source with line numbers, rehighlighted only when the page
+    /// content changes.
+    fn source_paragraph(&mut self) -> Paragraph<'static> {
+        let content = self.browser.content.as_deref().unwrap_or("");
+        if self
+            .source_cache
+            .as_ref()
+            .is_none_or(|(cached, _)| cached != content)
+        {
+            let lines = highlight_micron(content)
+                .into_iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let mut spans = vec![Span::styled(
+                        format!("{:>4} ", i + 1),
+                        Style::default().fg(Color::DarkGray),
+                    )];
+                    spans.extend(line.spans);
+                    Line::from(spans)
+                })
+                .collect();
+            self.source_cache = Some((content.to_string(), lines));
+        }
+        let lines = self
+            .source_cache
+            .as_ref()
+            .map(|(_, lines)| lines.clone())
+            .unwrap_or_default();
+        Paragraph::new(lines)
+    }
+
+    fn remember_form_data(&mut self, url: &str) {
+        let form_data = std::mem::take(&mut self.loading_form_data);
+        if form_data.is_empty() {
+            self.form_data_by_url.remove(url);
+        } else {
+            self.form_data_by_url.insert(url.to_string(), form_data);
+        }
+    }
+
+    /// Form data the current page was requested with.
+    pub fn page_form_data(&self) -> HashMap<String, String> {
+        self.browser
+            .url()
+            .and_then(|url| self.form_data_by_url.get(url))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_loading(
+        &mut self,
+        url: String,
+        request_id: u64,
+        form_data: HashMap<String, String>,
+    ) {
         self.loading_url = Some(url);
+        self.loading_form_data = form_data;
+        self.pending_request = Some(request_id);
+        self.cached_at = None;
+        self.showing_stale = false;
+        self.offline = false;
+        self.source_scroll = 0;
         self.browser.clear();
     }
 
     pub fn clear_loading(&mut self) {
         self.loading_url = None;
+        self.pending_request = None;
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.pending_request.is_some()
+    }
+
+    /// Whether a response for `request_id` belongs to the current navigation.
+    pub fn is_pending(&self, request_id: u64) -> bool {
+        self.pending_request == Some(request_id)
+    }
+
+    pub fn take_pending_request(&mut self) -> Option<u64> {
+        let request_id = self.pending_request.take();
+        self.loading_url = None;
+        self.showing_stale = false;
+        request_id
+    }
+
+    pub fn track_partial(&mut self, request_id: u64) {
+        self.pending_partials.insert(request_id, false);
+    }
+
+    pub fn is_partial_pending(&self, request_id: u64) -> bool {
+        self.pending_partials.contains_key(&request_id)
+    }
+
+    pub fn mark_partial_cached(&mut self, request_id: u64) {
+        if let Some(cached) = self.pending_partials.get_mut(&request_id) {
+            *cached = true;
+        }
+    }
+
+    /// Stops tracking a partial request, returning whether a cached copy of
+    /// it is already showing.
+    pub fn finish_partial(&mut self, request_id: u64) -> bool {
+        self.pending_partials.remove(&request_id).unwrap_or(false)
+    }
+
+    pub fn take_pending_partials(&mut self) -> Vec<u64> {
+        self.pending_partials.drain().map(|(id, _)| id).collect()
+    }
+
+    pub fn title(&self) -> String {
+        self.current_node
+            .as_ref()
+            .map(|n| n.name.clone())
+            .unwrap_or_else(|| "New Tab".to_string())
     }
 
     pub fn scroll_up(&mut self) {
-        self.browser.scroll_by(-1);
+        self.scroll_by(-1);
     }
 
     pub fn scroll_down(&mut self) {
-        self.browser.scroll_by(1);
+        self.scroll_by(1);
     }
 
     pub fn scroll_page_up(&mut self) {
-        self.browser
-            .scroll_by(-(self.last_content_area.height as i32));
+        self.scroll_by(-(self.last_content_area.height as i32));
     }
 
     pub fn scroll_page_down(&mut self) {
-        self.browser.scroll_by(self.last_content_area.height as i32);
+        self.scroll_by(self.last_content_area.height as i32);
+    }
+
+    fn scroll_by(&mut self, delta: i32) {
+        if self.show_source {
+            let max = self
+                .browser
+                .content
+                .as_deref()
+                .map_or(0, |c| c.lines().count().saturating_sub(1));
+            self.source_scroll = self
+                .source_scroll
+                .saturating_add_signed(delta as isize)
+                .min(max);
+            return;
+        }
+        self.browser.scroll_by(delta);
+    }
+
+    /// The page row at the top of the content area.
+    fn viewport_top(&self) -> usize {
+        if self.show_source {
+            self.source_scroll
+        } else {
+            self.browser.scroll_offset() as usize
+        }
+    }
+
+    pub fn is_finding(&self) -> bool {
+        self.find.is_some()
+    }
+
+    pub fn find_query(&self) -> &str {
+        self.find.as_ref().map_or("", |f| f.query.as_str())
+    }
+
+    /// Searches the page for `query` and scrolls to the first match at or
+    /// below the top of the viewport.
+    pub fn set_find_query(&mut self, query: &str) {
+        let mut find = self.find.take().unwrap_or_default();
+        self.capture_find_rows(&mut find, false);
+        find.query = query.to_string();
+        find.matches = find_matches(&find.rows, query);
+        find.select_from(self.viewport_top());
+        self.find = Some(find);
+        self.scroll_to_match();
+    }
+
+    pub fn find_next(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.select_next();
+        }
+        self.scroll_to_match();
+    }
+
+    pub fn find_prev(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.select_prev();
+        }
+        self.scroll_to_match();
+    }
+
+    pub fn clear_find(&mut self) {
+        self.find = None;
+    }
+
+    /// Recaptures the page text when the page, its width or its content has
+    /// changed since the last capture.
+    fn capture_find_rows(&mut self, find: &mut FindState, force: bool) {
+        let area = self.last_content_area;
+        let url = self.browser.url().map(|url| {
+            if self.show_source {
+                format!("source:{}", url)
+            } else {
+                url.to_string()
+            }
+        });
+        if !force && find.url == url && find.width == area.width && !find.rows.is_empty() {
+            return;
+        }
+        find.rows = if self.show_source {
+            capture_page(&self.source_paragraph(), area.width, area.height)
+        } else {
+            self.browser.resize(area.width, area.height);
+            self.browser
+                .render()
+                .map(|paragraph| capture_page(paragraph, area.width, area.height))
+                .unwrap_or_default()
+        };
+        find.width = area.width;
+        find.url = url;
+        find.matches = find_matches(&find.rows, &find.query);
+        if find.current >= find.matches.len() {
+            find.current = 0;
+        }
+    }
+
+    fn scroll_to_match(&mut self) {
+        let Some(m) = self.find.as_ref().and_then(FindState::current_match) else {
+            return;
+        };
+        let height = self.last_content_area.height as usize;
+        let top = self.viewport_top();
+        if (top..top + height).contains(&m.row) {
+            return;
+        }
+        let target = m.row.saturating_sub(height / 3);
+        self.scroll_by((target as i64 - top as i64) as i32);
+    }
+
+    fn render_inspector(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(Span::styled(
+                " Inspector ",
+                Style::default().fg(Color::White),
+            ))
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::DarkGray));
+        let value = |text: String| Span::styled(text, Style::default().fg(Color::Gray));
+        let heading = |text: &'static str| {
+            Line::from(Span::styled(
+                text,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        };
+
+        let response = self
+            .browser
+            .url()
+            .and_then(|url| self.responses_by_url.get(url));
+        let Some(response) = response else {
+            Paragraph::new(Line::from(Span::styled(
+                " No response yet",
+                Style::default().fg(Color::DarkGray),
+            )))
+            .render(inner, buf);
+            return;
+        };
+        let envelope = &response.envelope;
+
+        let round_trip = match response.round_trip {
+            Some(rtt) if rtt.as_millis() < 1000 => format!("{} ms", rtt.as_millis()),
+            Some(rtt) => format!("{:.1} s", rtt.as_secs_f64()),
+            None => "cached copy".to_string(),
+        };
+        let mut lines = vec![
+            heading(" Response"),
+            Line::from(vec![
+                label("  size        "),
+                value(format!(
+                    "{} ({} bytes)",
+                    super::format_bytes(envelope.size as u64),
+                    envelope.size
+                )),
+            ]),
+            Line::from(vec![label("  round trip  "), value(round_trip)]),
+            Line::from(""),
+            heading(" Envelope"),
+        ];
+
+        match (envelope.timestamp, &envelope.path) {
+            (Some(timestamp), Some(path)) => {
+                lines.push(Line::from(vec![
+                    label("  timestamp   "),
+                    value(format!("{:.3}", timestamp)),
+                ]));
+                if let Some(time) = chrono::DateTime::from_timestamp(timestamp as i64, 0) {
+                    lines.push(Line::from(vec![
+                        label("              "),
+                        value(
+                            time.with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d %H:%M:%S")
+                                .to_string(),
+                        ),
+                    ]));
+                }
+                lines.push(Line::from(vec![
+                    label("  path        "),
+                    value(path.clone()),
+                ]));
+            }
+            _ => lines.push(Line::from(Span::styled(
+                "  not a msgpack envelope",
+                Style::default().fg(Color::Yellow),
+            ))),
+        }
+        lines.push(Line::from(vec![
+            label("  content     "),
+            value(format!(
+                "{} bytes, {} lines",
+                envelope.content.len(),
+                envelope.content.lines().count()
+            )),
+        ]));
+
+        Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .render(inner, buf);
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let Some(hints) = &self.hints else {
+            return;
+        };
+        let typed_style = Style::default().fg(Color::DarkGray).bg(Color::Yellow);
+        let label_style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+
+        for target in &hints.targets {
+            let Some(rest) = target.label.strip_prefix(hints.typed.as_str()) else {
+                continue;
+            };
+            let x = area.x + target.x;
+            let y = area.y + target.y;
+            let max_width = (area.x + area.width - x) as usize;
+            let (x, _) = buf.set_stringn(x, y, &hints.typed, max_width, typed_style);
+            let max_width = (area.x + area.width).saturating_sub(x) as usize;
+            buf.set_stringn(x, y, rest, max_width, label_style);
+        }
+    }
+
+    /// Paints the visible matches over the page once it has rendered.
+    fn render_find(&mut self, content_area: Rect, buf: &mut Buffer) {
+        let Some(mut find) = self.find.take() else {
+            return;
+        };
+
+        self.capture_find_rows(&mut find, false);
+        let top = self.viewport_top();
+        let visible = capture_viewport(buf, content_area);
+        if !viewport_matches(&find.rows, &visible, top) {
+            // The page changed underneath us, e.g. a partial loaded.
+            self.capture_find_rows(&mut find, true);
+        }
+        let current = find.current_match();
+        let bottom = top + content_area.height as usize;
+        for m in find
+            .matches
+            .iter()
+            .filter(|m| (top..bottom).contains(&m.row))
+        {
+            let style = if Some(*m) == current {
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            };
+            let y = content_area.y + (m.row - top) as u16;
+            for x in m.start..m.end.min(content_area.width) {
+                buf[(content_area.x + x, y)].set_style(style);
+            }
+        }
+        self.find = Some(find);
     }
 
     pub fn select_next(&mut self) {
@@ -114,10 +619,15 @@ impl BrowserView {
     }
 
     pub fn go_back(&mut self) -> bool {
+        // Set again from the cache entry once the revalidation finds it.
+        self.cached_at = None;
+        self.source_scroll = 0;
         self.browser.back()
     }
 
     pub fn go_forward(&mut self) -> bool {
+        self.cached_at = None;
+        self.source_scroll = 0;
         self.browser.forward()
     }
 
@@ -129,6 +639,9 @@ impl BrowserView {
         if self.last_fwd_btn_area.intersects(point) && self.browser.can_go_forward() {
             return Some(NavAction::Forward);
         }
+        if self.last_reload_btn_area.intersects(point) && self.is_loading() {
+            return Some(NavAction::Stop);
+        }
         if self.last_reload_btn_area.intersects(point) && self.browser.url().is_some() {
             return Some(NavAction::Reload);
         }
@@ -228,10 +741,12 @@ impl Widget for &mut BrowserView {
         } else {
             Style::default().fg(Color::DarkGray)
         };
-        let reload_style = if self.browser.url().is_some() {
-            Style::default().fg(Color::Cyan)
+        let (reload_text, reload_style) = if self.is_loading() {
+            ("[\u{2715}]", Style::default().fg(Color::Yellow))
+        } else if self.browser.url().is_some() {
+            ("[\u{21bb}]", Style::default().fg(Color::Cyan))
         } else {
-            Style::default().fg(Color::DarkGray)
+            ("[\u{21bb}]", Style::default().fg(Color::DarkGray))
         };
 
         let url_span = if let Some(loading) = &self.loading_url {
@@ -240,10 +755,49 @@ impl Widget for &mut BrowserView {
                 Span::styled(loading.clone(), Style::default().fg(Color::Yellow)),
             ]
         } else if let Some(url) = self.browser.url() {
-            vec![Span::styled(
+            let mut spans = Vec::new();
+            if self.show_source {
+                spans.push(Span::styled(
+                    "SOURCE",
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::raw(" "));
+            }
+            if self.offline {
+                spans.push(Span::styled(
+                    "OFFLINE ",
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Red)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(
                 url.to_string(),
                 Style::default().fg(Color::DarkGray),
-            )]
+            ));
+            if let Some(fetched_at) = self.cached_at {
+                let color = if self.offline {
+                    Color::Red
+                } else {
+                    Color::Yellow
+                };
+                spans.push(Span::styled(
+                    format!("  {}", cached_badge(fetched_at)),
+                    Style::default().fg(color),
+                ));
+            }
+            if self.showing_stale && self.is_loading() {
+                spans.push(Span::styled(
+                    " \u{21bb}",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            spans
         } else {
             vec![Span::styled(
                 "No page loaded",
@@ -256,7 +810,7 @@ impl Widget for &mut BrowserView {
             Span::raw(" "),
             Span::styled("[\u{25b6}]", fwd_style),
             Span::raw(" "),
-            Span::styled("[\u{21bb}]", reload_style),
+            Span::styled(reload_text, reload_style),
             Span::raw("  "),
         ];
         nav_spans.extend(url_span);
@@ -321,17 +875,36 @@ impl Widget for &mut BrowserView {
             1,
         );
 
-        let content_area = Rect::new(
+        let mut content_area = Rect::new(
             inner.x,
             inner.y + 2,
             inner.width,
             inner.height.saturating_sub(3),
         );
 
+        if self.inspector_open && content_area.width >= 60 {
+            let inspector_width = (content_area.width / 3).max(34);
+            let inspector_area = Rect::new(
+                content_area.x + content_area.width - inspector_width,
+                content_area.y,
+                inspector_width,
+                content_area.height,
+            );
+            content_area.width -= inspector_width;
+            self.render_inspector(inspector_area, buf);
+        }
+
         self.last_content_area = content_area;
         self.browser.resize(content_area.width, content_area.height);
-        if let Some(paragraph) = self.browser.render() {
+        if self.show_source {
+            self.source_paragraph()
+                .scroll((self.source_scroll.min(u16::MAX as usize) as u16, 0))
+                .render(content_area, buf);
+            self.render_find(content_area, buf);
+        } else if let Some(paragraph) = self.browser.render() {
             paragraph.clone().render(content_area, buf);
+            self.render_find(content_area, buf);
+            self.render_hints(content_area, buf);
         } else {
             Paragraph::new("No content")
                 .style(Style::default().fg(Color::DarkGray))
@@ -355,5 +928,41 @@ impl Widget for &mut BrowserView {
         Paragraph::new(link_text)
             .style(Style::default().fg(Color::DarkGray))
             .render(link_bar_area, buf);
+
+        if let Some(find) = &self.find {
+            let find_line = Line::from(vec![
+                Span::styled("/", Style::default().fg(Color::Magenta)),
+                Span::styled(find.query.clone(), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {}", find.position_label()),
+                    Style::default().fg(if find.matches.is_empty() {
+                        Color::Red
+                    } else {
+                        Color::Yellow
+                    }),
+                ),
+            ]);
+            let width = (find_line.width() as u16).min(link_bar_area.width);
+            let find_area = Rect::new(
+                link_bar_area.x + link_bar_area.width - width,
+                link_bar_area.y,
+                width,
+                1,
+            );
+            Clear.render(find_area, buf);
+            Paragraph::new(find_line).render(find_area, buf);
+        }
+    }
+}
+
+fn cached_badge(fetched_at: i64) -> String {
+    let age = (chrono::Utc::now().timestamp() - fetched_at).max(0);
+    match age {
+        0..60 => "cached just now".to_string(),
+        60..120 => "cached 1 minute ago".to_string(),
+        120..3600 => format!("cached {} minutes ago", age / 60),
+        3600..7200 => "cached 1 hour ago".to_string(),
+        7200..86400 => format!("cached {} hours ago", age / 3600),
+        _ => format!("cached {} days ago", age / 86400),
     }
 }
@@ -0,0 +1,472 @@
+use crate::messages::{Conversation, MessageState, StoredMessage};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Widget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationClick {
+    None,
+    Thread,
+    Compose,
+}
+
+pub struct ConversationsView {
+    address: [u8; 16],
+    /// Most recently active first.
+    conversations: Vec<Conversation>,
+    list_state: ListState,
+    /// Lines scrolled back from the newest message.
+    scroll: usize,
+    last_list_area: Rect,
+    last_compose_area: Rect,
+}
+
+impl Default for ConversationsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConversationsView {
+    pub fn new() -> Self {
+        Self {
+            address: [0; 16],
+            conversations: Vec::new(),
+            list_state: ListState::default(),
+            scroll: 0,
+            last_list_area: Rect::default(),
+            last_compose_area: Rect::default(),
+        }
+    }
+
+    pub fn address(&self) -> [u8; 16] {
+        self.address
+    }
+
+    pub fn set_conversations(&mut self, address: [u8; 16], conversations: Vec<Conversation>) {
+        self.address = address;
+        self.conversations = conversations;
+        self.resort(None);
+        self.list_state.select(Some(0));
+    }
+
+    /// Selects the conversation with `peer`, starting an empty one if there
+    /// isn't one yet.
+    pub fn open(&mut self, peer: [u8; 16]) {
+        if self.position(peer).is_none() {
+            self.conversations.push(Conversation::new(peer));
+        }
+        self.resort(Some(peer));
+        self.scroll = 0;
+    }
+
+    pub fn selected_peer(&self) -> Option<[u8; 16]> {
+        self.selected().and_then(|c| c.peer_hash())
+    }
+
+    /// Clears the unread count of the open conversation, returning its peer
+    /// if there was anything to clear.
+    pub fn take_unread(&mut self) -> Option<[u8; 16]> {
+        let idx = self.list_state.selected()?;
+        let conversation = self.conversations.get_mut(idx)?;
+        if conversation.unread == 0 {
+            return None;
+        }
+        conversation.unread = 0;
+        conversation.peer_hash()
+    }
+
+    pub fn unread_total(&self) -> usize {
+        self.conversations.iter().map(|c| c.unread).sum()
+    }
+
+    pub fn receive(&mut self, peer: [u8; 16], message: StoredMessage) {
+        self.push(peer, message, true);
+    }
+
+    pub fn push_outgoing(&mut self, peer: [u8; 16], message: StoredMessage) {
+        self.push(peer, message, false);
+        self.scroll = 0;
+    }
+
+    pub fn set_state(&mut self, peer: [u8; 16], id: &str, state: MessageState) {
+        if let Some(idx) = self.position(peer) {
+            self.conversations[idx].set_state(id, state);
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.select_offset(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.select_offset(-1);
+    }
+
+    pub fn scroll_thread_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_add(lines);
+    }
+
+    pub fn scroll_thread_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    pub fn compose_area(&self) -> Rect {
+        self.last_compose_area
+    }
+
+    pub fn click(&mut self, x: u16, y: u16) -> ConversationClick {
+        if self.selected().is_some() && self.last_compose_area.contains((x, y).into()) {
+            return ConversationClick::Compose;
+        }
+        let area = self.last_list_area;
+        if !area.contains((x, y).into()) {
+            return ConversationClick::None;
+        }
+        // Each entry is two lines tall.
+        let row = (y - area.y) as usize / 2 + self.list_state.offset();
+        if row < self.conversations.len() {
+            self.list_state.select(Some(row));
+            self.scroll = 0;
+            return ConversationClick::Thread;
+        }
+        ConversationClick::None
+    }
+
+    fn selected(&self) -> Option<&Conversation> {
+        self.conversations.get(self.list_state.selected()?)
+    }
+
+    fn position(&self, peer: [u8; 16]) -> Option<usize> {
+        let key = hex::encode(peer);
+        self.conversations.iter().position(|c| c.peer == key)
+    }
+
+    fn push(&mut self, peer: [u8; 16], message: StoredMessage, incoming: bool) {
+        let selected = self.selected_peer();
+        let idx = match self.position(peer) {
+            Some(idx) => idx,
+            None => {
+                self.conversations.push(Conversation::new(peer));
+                self.conversations.len() - 1
+            }
+        };
+        if self.conversations[idx].push(message) && incoming {
+            self.conversations[idx].unread += 1;
+        }
+        self.resort(selected.or(Some(peer)));
+    }
+
+    /// Orders by recent activity, keeping `keep` selected.
+    fn resort(&mut self, keep: Option<[u8; 16]>) {
+        self.conversations.sort_by(|a, b| {
+            b.last_activity()
+                .partial_cmp(&a.last_activity())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        if let Some(idx) = keep.and_then(|peer| self.position(peer)) {
+            self.list_state.select(Some(idx));
+        }
+    }
+
+    fn select_offset(&mut self, delta: isize) {
+        let len = self.conversations.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).rem_euclid(len as isize) as usize;
+        self.list_state.select(Some(next));
+        self.scroll = 0;
+    }
+}
+
+impl Widget for &mut ConversationsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let chunks = Layout::horizontal([Constraint::Length(34), Constraint::Min(20)]).split(area);
+        self.render_list(chunks[0], buf);
+        self.render_thread(chunks[1], buf);
+    }
+}
+
+impl ConversationsView {
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut title = vec![Span::styled(
+            " Conversations ",
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )];
+        let unread = self.unread_total();
+        if unread > 0 {
+            title.push(Span::styled(
+                format!("({} unread) ", unread),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        let block = Block::default()
+            .title(Line::from(title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        self.last_list_area = inner;
+
+        if self.conversations.is_empty() {
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "No conversations yet",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Open a nomad@ link to start one",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(inner, buf);
+            return;
+        }
+
+        let preview_width = inner.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = self
+            .conversations
+            .iter()
+            .map(|conversation| {
+                let mut header = vec![Span::styled(
+                    format!(" {}", short_hash(&conversation.peer)),
+                    Style::default().fg(Color::White),
+                )];
+                if conversation.unread > 0 {
+                    header.push(Span::styled(
+                        format!("  \u{25CF} {}", conversation.unread),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                let preview = conversation
+                    .messages
+                    .last()
+                    .map(|m| {
+                        let prefix = if m.outgoing { "You: " } else { "" };
+                        let text = format!("{}{}", prefix, m.content.replace('\n', " "));
+                        text.chars().take(preview_width).collect::<String>()
+                    })
+                    .unwrap_or_default();
+                ListItem::new(vec![
+                    Line::from(header),
+                    Line::from(Span::styled(
+                        format!(" {}", preview),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ])
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("");
+
+        ratatui::widgets::StatefulWidget::render(list, inner, buf, &mut self.list_state);
+    }
+
+    fn render_thread(&mut self, area: Rect, buf: &mut Buffer) {
+        self.last_compose_area = Rect::default();
+
+        let title = match self.selected() {
+            Some(conversation) => Line::from(vec![
+                Span::styled(" nomad@", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{} ", conversation.peer),
+                    Style::default().fg(Color::White),
+                ),
+            ]),
+            None => Line::from(Span::styled(
+                " Messages ",
+                Style::default().fg(Color::White),
+            )),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(Span::styled(
+                format!(" Your address: nomad@{} ", hex::encode(self.address)),
+                Style::default().fg(Color::DarkGray),
+            )))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(conversation) = self.selected().cloned() else {
+            Paragraph::new(Line::from(Span::styled(
+                "Select a conversation",
+                Style::default().fg(Color::DarkGray),
+            )))
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(inner, buf);
+            return;
+        };
+
+        let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(3)]).split(inner);
+        let lines = thread_lines(&conversation, chunks[0].width.saturating_sub(2) as usize);
+        let height = chunks[0].height as usize;
+        let max_scroll = lines.len().saturating_sub(height);
+        self.scroll = self.scroll.min(max_scroll);
+        let end = lines.len() - self.scroll;
+        let start = end.saturating_sub(height);
+        Paragraph::new(lines[start..end].to_vec()).render(chunks[0], buf);
+
+        let compose = Block::default()
+            .title(Span::styled(" Message ", Style::default().fg(Color::White)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let compose_inner = compose.inner(chunks[1]);
+        compose.render(chunks[1], buf);
+        Paragraph::new(Span::styled(
+            "Press c to write a message",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .render(compose_inner, buf);
+        self.last_compose_area = compose_inner;
+    }
+}
+
+fn thread_lines(conversation: &Conversation, width: usize) -> Vec<Line<'static>> {
+    let peer = short_hash(&conversation.peer);
+    let mut lines = Vec::new();
+    for message in &conversation.messages {
+        let (author, color) = if message.outgoing {
+            ("You".to_string(), Color::Cyan)
+        } else {
+            (peer.clone(), Color::Green)
+        };
+        let mut header = vec![
+            Span::styled(
+                format!(" {}", author),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {}", format_message_time(message.timestamp)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        match message.state {
+            MessageState::Sending => header.push(Span::styled(
+                "  sending\u{2026}",
+                Style::default().fg(Color::DarkGray),
+            )),
+            MessageState::Delivered => header.push(Span::styled(
+                "  \u{2713}",
+                Style::default().fg(Color::Green),
+            )),
+            MessageState::Failed => header.push(Span::styled(
+                "  \u{2717} not delivered",
+                Style::default().fg(Color::Red),
+            )),
+            MessageState::Received => {}
+        }
+        lines.push(Line::from(header));
+
+        if !message.title.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", message.title),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        for text in message.content.lines() {
+            for wrapped in wrap(text, width) {
+                lines.push(Line::from(Span::styled(
+                    format!(" {}", wrapped),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// Greedy word wrap; words longer than the width are split.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        let needed = current.chars().count() + usize::from(!current.is_empty()) + word.len();
+        if needed > width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        while word.len() > width {
+            lines.push(word.drain(..width).collect());
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.extend(word);
+    }
+    lines.push(current);
+    lines
+}
+
+fn short_hash(hex: &str) -> String {
+    if hex.len() <= 16 {
+        return hex.to_string();
+    }
+    format!("{}\u{2026}{}", &hex[..8], &hex[hex.len() - 6..])
+}
+
+fn format_message_time(timestamp: f64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::DirectMessage;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("hello mesh world", 11), ["hello mesh", "world"]);
+        assert_eq!(wrap("abcdefgh", 3), ["abc", "def", "gh"]);
+        assert_eq!(wrap("", 10), [""]);
+    }
+
+    #[test]
+    fn test_incoming_message_moves_thread_up_and_keeps_selection() {
+        let mut view = ConversationsView::new();
+        let (a, b) = ([1; 16], [2; 16]);
+        view.open(a);
+        view.open(b);
+        assert_eq!(view.selected_peer(), Some(b));
+
+        let message = DirectMessage::new([9; 16], a, "", "ping");
+        view.receive(a, StoredMessage::incoming(&message));
+        assert_eq!(view.conversations[0].peer, hex::encode(a));
+        assert_eq!(view.selected_peer(), Some(b));
+        assert_eq!(view.unread_total(), 1);
+
+        view.open(a);
+        assert_eq!(view.take_unread(), Some(a));
+        assert_eq!(view.take_unread(), None);
+    }
+}
@@ -1,5 +1,6 @@
+use super::fuzzy;
 use super::modal::{Modal, ModalButton};
-use crate::network::NodeInfo;
+use crate::network::{Announce, AnnounceKind, NodeInfo};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -17,11 +18,50 @@ pub enum ModalAction {
     Connect,
     Save,
     Copy,
+    Message,
     Dismiss,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Recent,
+    Name,
+    Distance,
+}
+
+impl SortOrder {
+    fn next(self) -> Self {
+        match self {
+            SortOrder::Recent => SortOrder::Name,
+            SortOrder::Name => SortOrder::Distance,
+            SortOrder::Distance => SortOrder::Recent,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Recent => "recent",
+            SortOrder::Name => "name",
+            SortOrder::Distance => "hops",
+        }
+    }
+}
+
+/// A line of the list: a section heading or an entry in `visible()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Header(AnnounceKind, usize),
+    Entry(usize),
+}
+
 pub struct DiscoveryView {
-    nodes: Vec<NodeInfo>,
+    announces: Vec<Announce>,
+    /// Only show this kind, or everything when `None`.
+    kind_filter: Option<AnnounceKind>,
+    /// Text typed after `/`, matched against names and hashes.
+    filter: String,
+    sort: SortOrder,
+    /// Index into `visible()`.
     selected: usize,
     scroll_offset: usize,
     modal_open: bool,
@@ -40,7 +80,10 @@ impl Default for DiscoveryView {
 impl DiscoveryView {
     pub fn new() -> Self {
         Self {
-            nodes: Vec::new(),
+            announces: Vec::new(),
+            kind_filter: None,
+            filter: String::new(),
+            sort: SortOrder::Recent,
             selected: 0,
             scroll_offset: 0,
             modal_open: false,
@@ -51,24 +94,87 @@ impl DiscoveryView {
         }
     }
 
-    pub fn add_node(&mut self, node: NodeInfo) {
-        if let Some(existing) = self.nodes.iter_mut().find(|n| n.hash == node.hash) {
-            existing.name = node.name;
+    pub fn set_announces(&mut self, announces: Vec<Announce>) {
+        self.announces = announces;
+        self.selected = 0;
+        self.scroll_offset = 0;
+    }
+
+    pub fn add(&mut self, announce: Announce) {
+        let selected = self.selected_announce().map(|a| a.hash);
+        if let Some(existing) = self.announces.iter_mut().find(|a| a.hash == announce.hash) {
+            *existing = announce;
         } else {
-            self.nodes.push(node);
+            self.announces.push(announce);
         }
+        self.reselect(selected);
     }
 
-    pub fn nodes(&self) -> &[NodeInfo] {
-        &self.nodes
+    /// Every discovered node, named or not, for resolving links.
+    pub fn nodes(&self) -> Vec<NodeInfo> {
+        self.announces
+            .iter()
+            .filter_map(Announce::node_info)
+            .collect()
     }
 
     pub fn node_count(&self) -> usize {
-        self.nodes.len()
+        self.count(AnnounceKind::Node)
+    }
+
+    pub fn selected_announce(&self) -> Option<&Announce> {
+        self.visible().get(self.selected).copied()
+    }
+
+    pub fn selected_node(&self) -> Option<NodeInfo> {
+        self.selected_announce().and_then(Announce::node_info)
+    }
+
+    pub fn kind_filter(&self) -> Option<AnnounceKind> {
+        self.kind_filter
+    }
+
+    /// Steps through showing everything, then each kind on its own.
+    pub fn cycle_kind_filter(&mut self) {
+        let selected = self.selected_announce().map(|a| a.hash);
+        self.kind_filter = match self.kind_filter {
+            None => Some(AnnounceKind::ALL[0]),
+            Some(kind) => AnnounceKind::ALL
+                .iter()
+                .position(|k| *k == kind)
+                .and_then(|i| AnnounceKind::ALL.get(i + 1))
+                .copied(),
+        };
+        self.scroll_offset = 0;
+        self.reselect(selected);
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Narrows the list as the user types, keeping the selection if it
+    /// still matches.
+    pub fn set_filter(&mut self, filter: &str) {
+        if self.filter == filter {
+            return;
+        }
+        let selected = self.selected_announce().map(|a| a.hash);
+        self.filter = filter.to_string();
+        self.scroll_offset = 0;
+        self.reselect(selected);
+        self.adjust_scroll();
+    }
+
+    pub fn sort(&self) -> SortOrder {
+        self.sort
     }
 
-    pub fn selected_node(&self) -> Option<&NodeInfo> {
-        self.nodes.get(self.selected)
+    pub fn cycle_sort(&mut self) {
+        let selected = self.selected_announce().map(|a| a.hash);
+        self.sort = self.sort.next();
+        self.reselect(selected);
+        self.adjust_scroll();
     }
 
     pub fn is_modal_open(&self) -> bool {
@@ -76,58 +182,159 @@ impl DiscoveryView {
     }
 
     pub fn select_next(&mut self) {
+        let len = self.visible().len();
         if self.modal_open {
-            self.modal_selected = (self.modal_selected + 1) % 4;
-        } else if !self.nodes.is_empty() {
-            self.selected = (self.selected + 1) % self.nodes.len();
+            self.modal_selected = (self.modal_selected + 1) % self.modal_actions().len();
+        } else if len > 0 {
+            self.selected = (self.selected + 1) % len;
             self.adjust_scroll();
         }
     }
 
     pub fn select_prev(&mut self) {
+        let len = self.visible().len();
         if self.modal_open {
-            self.modal_selected = if self.modal_selected == 0 {
-                3
-            } else {
-                self.modal_selected - 1
-            };
-        } else if !self.nodes.is_empty() {
-            self.selected = self.selected.checked_sub(1).unwrap_or(self.nodes.len() - 1);
+            let count = self.modal_actions().len();
+            self.modal_selected = (self.modal_selected + count - 1) % count;
+        } else if len > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(len - 1);
             self.adjust_scroll();
         }
     }
 
-    fn adjust_scroll(&mut self) {
-        if self.last_height == 0 {
-            return;
+    fn count(&self, kind: AnnounceKind) -> usize {
+        self.announces.iter().filter(|a| a.kind == kind).count()
+    }
+
+    /// Announces passing the filters, grouped by kind in section order and
+    /// sorted within each section, best matches first while filtering.
+    fn visible(&self) -> Vec<&Announce> {
+        let mut visible: Vec<(&Announce, u32)> = self
+            .announces
+            .iter()
+            .filter(|a| self.kind_filter.is_none_or(|f| f == a.kind))
+            .filter_map(|a| {
+                fuzzy::score(&self.filter, &a.display_name(), &a.hash_hex()).map(|s| (a, s))
+            })
+            .collect();
+        let section = |a: &Announce| AnnounceKind::ALL.iter().position(|k| *k == a.kind);
+        if !self.filter.is_empty() {
+            visible.sort_by_key(|&(a, score)| {
+                (
+                    section(a),
+                    std::cmp::Reverse(score),
+                    std::cmp::Reverse(a.last_seen),
+                )
+            });
+            return visible.into_iter().map(|(a, _)| a).collect();
         }
-        if self.selected < self.scroll_offset {
-            self.scroll_offset = self.selected;
-        } else if self.selected >= self.scroll_offset + self.last_height {
-            self.scroll_offset = self.selected - self.last_height + 1;
+
+        let mut visible: Vec<&Announce> = visible.into_iter().map(|(a, _)| a).collect();
+        match self.sort {
+            SortOrder::Recent => {
+                visible.sort_by_key(|a| (section(a), std::cmp::Reverse(a.last_seen)))
+            }
+            SortOrder::Name => visible.sort_by_key(|a| {
+                (
+                    section(a),
+                    a.name.is_none(),
+                    a.display_name().to_lowercase(),
+                )
+            }),
+            // Unknown distances go last.
+            SortOrder::Distance => visible.sort_by_key(|a| {
+                (
+                    section(a),
+                    a.hops.unwrap_or(u8::MAX),
+                    std::cmp::Reverse(a.last_seen),
+                )
+            }),
         }
+        visible
     }
 
-    pub fn scroll_up(&mut self) {
-        if self.scroll_offset > 0 {
-            self.scroll_offset -= 1;
+    fn rows(&self) -> Vec<Row> {
+        let visible = self.visible();
+        let mut rows = Vec::with_capacity(visible.len() + AnnounceKind::ALL.len());
+        for (idx, announce) in visible.iter().enumerate() {
+            if idx == 0 || visible[idx - 1].kind != announce.kind {
+                let count = visible[idx..]
+                    .iter()
+                    .take_while(|a| a.kind == announce.kind)
+                    .count();
+                rows.push(Row::Header(announce.kind, count));
+            }
+            rows.push(Row::Entry(idx));
         }
+        rows
     }
 
-    pub fn scroll_down(&mut self) {
+    fn selected_row(&self) -> usize {
+        self.rows()
+            .iter()
+            .position(|row| *row == Row::Entry(self.selected))
+            .unwrap_or(0)
+    }
+
+    /// Keeps `hash` selected after the list changed under it.
+    fn reselect(&mut self, hash: Option<[u8; 16]>) {
+        let visible = self.visible();
+        self.selected = hash
+            .and_then(|hash| visible.iter().position(|a| a.hash == hash))
+            .unwrap_or(0)
+            .min(visible.len().saturating_sub(1));
+    }
+
+    fn adjust_scroll(&mut self) {
         if self.last_height == 0 {
             return;
         }
-        let max_scroll = self.nodes.len().saturating_sub(self.last_height);
-        if self.scroll_offset < max_scroll {
-            self.scroll_offset += 1;
+        let row = self.selected_row();
+        // Keep the section heading in view above the first entry.
+        let top = if row > 0 && matches!(self.rows()[row - 1], Row::Header(..)) {
+            row - 1
+        } else {
+            row
+        };
+        if top < self.scroll_offset {
+            self.scroll_offset = top;
+        } else if row >= self.scroll_offset + self.last_height {
+            self.scroll_offset = row - self.last_height + 1;
         }
     }
 
+    pub fn scroll_up(&mut self) {
+        self.select_prev();
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.select_next();
+    }
+
     pub fn open_modal(&mut self) {
-        if !self.nodes.is_empty() {
+        if self.selected_announce().is_some() {
             self.modal_open = true;
-            self.modal_selected = 3;
+            self.modal_selected = self.modal_actions().len() - 1;
+        }
+    }
+
+    /// Buttons for the selected announce, left to right; the last is the default.
+    fn modal_actions(&self) -> &'static [ModalAction] {
+        match self.selected_announce().map(|a| a.kind) {
+            Some(AnnounceKind::Node) => &[
+                ModalAction::Dismiss,
+                ModalAction::Copy,
+                ModalAction::Save,
+                ModalAction::Connect,
+            ],
+            Some(AnnounceKind::Peer) => &[
+                ModalAction::Dismiss,
+                ModalAction::Copy,
+                ModalAction::Message,
+            ],
+            Some(AnnounceKind::PropagationNode) | None => {
+                &[ModalAction::Dismiss, ModalAction::Copy]
+            }
         }
     }
 
@@ -139,13 +346,10 @@ impl DiscoveryView {
         if !self.modal_open {
             return ModalAction::None;
         }
-        match self.modal_selected {
-            0 => ModalAction::Dismiss,
-            1 => ModalAction::Copy,
-            2 => ModalAction::Save,
-            3 => ModalAction::Connect,
-            _ => ModalAction::None,
-        }
+        self.modal_actions()
+            .get(self.modal_selected)
+            .copied()
+            .unwrap_or(ModalAction::None)
     }
 
     pub fn click(&mut self, x: u16, y: u16, _area: Rect) -> Option<usize> {
@@ -165,13 +369,12 @@ impl DiscoveryView {
         }
 
         let inner_y = y.saturating_sub(list_inner.y);
-        let idx = self.scroll_offset + inner_y as usize;
-
-        if idx < self.nodes.len() {
-            self.selected = idx;
-            Some(idx)
-        } else {
-            None
+        match self.rows().get(self.scroll_offset + inner_y as usize) {
+            Some(Row::Entry(idx)) => {
+                self.selected = *idx;
+                Some(*idx)
+            }
+            _ => None,
         }
     }
 
@@ -181,29 +384,22 @@ impl DiscoveryView {
         }
 
         let modal = self.build_modal();
-        if let Some(idx) = modal.hit_test_buttons(x, y, self.last_modal_area) {
-            match idx {
-                0 => ModalAction::Dismiss,
-                1 => ModalAction::Copy,
-                2 => ModalAction::Save,
-                3 => ModalAction::Connect,
-                _ => ModalAction::None,
-            }
-        } else {
-            ModalAction::None
-        }
+        modal
+            .hit_test_buttons(x, y, self.last_modal_area)
+            .and_then(|idx| self.modal_actions().get(idx).copied())
+            .unwrap_or(ModalAction::None)
     }
 
     fn build_modal(&self) -> Modal<'_> {
-        let node = self.selected_node().unwrap();
-        let hash_hex = node.hash_hex();
+        let announce = self.selected_announce().unwrap();
+        let hash_hex = announce.hash_hex();
 
         let content = vec![
             Line::from(""),
             Line::from(vec![
                 Span::styled("  Name: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(
-                    &node.name,
+                    announce.display_name(),
                     Style::default()
                         .fg(Color::White)
                         .add_modifier(Modifier::BOLD),
@@ -223,44 +419,99 @@ impl DiscoveryView {
                 Style::default().fg(Color::Cyan),
             )]),
             Line::from(""),
-            Line::from(""),
+            Line::from(vec![
+                Span::styled("  Announces: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!(
+                        "{}, first seen {}",
+                        announce.announce_count,
+                        format_ago(chrono::Utc::now().timestamp() - announce.first_seen)
+                    ),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]),
         ];
 
-        Modal::new("Node")
+        let title = match announce.kind {
+            AnnounceKind::Node => "Node",
+            AnnounceKind::Peer => "Peer",
+            AnnounceKind::PropagationNode => "Propagation Node",
+        };
+        let buttons = self
+            .modal_actions()
+            .iter()
+            .map(|action| match action {
+                ModalAction::Dismiss | ModalAction::None => {
+                    ModalButton::new("Cancel", Color::DarkGray)
+                }
+                ModalAction::Copy => ModalButton::new("Copy", Color::Cyan),
+                ModalAction::Save => ModalButton::new("Save", Color::Green),
+                ModalAction::Connect => ModalButton::new("Connect", Color::Magenta),
+                ModalAction::Message => ModalButton::new("Message", Color::Magenta),
+            })
+            .collect();
+
+        Modal::new(title)
             .content(content)
-            .buttons(vec![
-                ModalButton::new("Cancel", Color::DarkGray),
-                ModalButton::new("Copy", Color::Cyan),
-                ModalButton::new("Save", Color::Green),
-                ModalButton::new("Connect", Color::Magenta),
-            ])
+            .buttons(buttons)
             .selected(self.modal_selected)
     }
 
     pub fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
         self.last_list_area = area;
 
-        let block = Block::default()
-            .title(Line::from(vec![
-                Span::styled(
-                    " Discovered Nodes ",
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    format!("({}) ", self.nodes.len()),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ]))
+        let shown = self.visible().len();
+        let count = if shown == self.announces.len() {
+            format!("({}) ", shown)
+        } else {
+            format!("({} of {}) ", shown, self.announces.len())
+        };
+        let mut title = vec![
+            Span::styled(
+                " Discovered ",
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(count, Style::default().fg(Color::DarkGray)),
+        ];
+        title.push(Span::styled(
+            format!("\u{00B7} by {} ", self.sort.label()),
+            Style::default().fg(Color::DarkGray),
+        ));
+        if let Some(kind) = self.kind_filter {
+            title.push(Span::styled(
+                format!("\u{00B7} {} only ", kind.label()),
+                Style::default().fg(badge_color(kind)),
+            ));
+        }
+
+        let mut block = Block::default()
+            .title(Line::from(title))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray));
+        if !self.filter.is_empty() {
+            block = block.title_bottom(filter_title(&self.filter));
+        }
 
         let inner = block.inner(area);
         self.last_height = inner.height as usize;
         block.render(area, buf);
 
-        if self.nodes.is_empty() {
+        let rows = self.rows();
+        if rows.is_empty() && !self.announces.is_empty() {
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Nothing matches",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(inner, buf);
+            return;
+        }
+        if rows.is_empty() {
             let empty_lines = vec![
                 Line::from(""),
                 Line::from(Span::styled(
@@ -279,22 +530,62 @@ impl DiscoveryView {
             return;
         }
 
-        let items: Vec<ListItem> = self
-            .nodes
+        let visible = self.visible();
+        let now = chrono::Utc::now().timestamp();
+        let items: Vec<ListItem> = rows
             .iter()
             .skip(self.scroll_offset)
             .take(inner.height as usize)
-            .map(|node| {
-                let hash_short = format!("{}..{}", &node.hash_hex()[..6], &node.hash_hex()[26..]);
-
-                ListItem::new(Line::from(vec![
-                    Span::styled(" \u{2022} ", Style::default().fg(Color::Magenta)),
-                    Span::styled(&node.name, Style::default().fg(Color::Gray)),
+            .map(|row| match *row {
+                Row::Header(kind, count) => ListItem::new(Line::from(vec![
                     Span::styled(
-                        format!("  {}", hash_short),
-                        Style::default().fg(Color::DarkGray),
+                        format!(" {} ", kind.label()),
+                        Style::default()
+                            .fg(badge_color(kind))
+                            .add_modifier(Modifier::BOLD),
                     ),
-                ]))
+                    Span::styled(format!("({})", count), Style::default().fg(Color::DarkGray)),
+                ])),
+                Row::Entry(idx) => {
+                    let announce = visible[idx];
+                    let hash = announce.hash_hex();
+                    let hash_short = format!("{}..{}", &hash[..6], &hash[26..]);
+                    let name_style = if announce.name.is_some() {
+                        Style::default().fg(Color::Gray)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    };
+
+                    let mut seen = format!("  seen {}", format_ago(now - announce.last_seen));
+                    if let Some(hops) = announce.hops {
+                        seen.push_str(&format!(
+                            " \u{00B7} {} hop{}",
+                            hops,
+                            if hops == 1 { "" } else { "s" }
+                        ));
+                    }
+                    if let Some(interface) = &announce.interface {
+                        seen.push_str(&format!(" \u{00B7} {}", interface));
+                    }
+
+                    let item = ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("  {:<4} ", announce.kind.badge()),
+                            Style::default().fg(badge_color(announce.kind)),
+                        ),
+                        Span::styled(announce.display_name(), name_style),
+                        Span::styled(
+                            format!("  {}", hash_short),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::styled(seen, Style::default().fg(Color::DarkGray)),
+                    ]));
+                    if idx == self.selected {
+                        item.style(Style::default().bg(Color::DarkGray))
+                    } else {
+                        item
+                    }
+                }
             })
             .collect();
 
@@ -303,7 +594,7 @@ impl DiscoveryView {
     }
 
     pub fn render_modal(&mut self, area: Rect, buf: &mut Buffer) {
-        if !self.modal_open || self.selected_node().is_none() {
+        if !self.modal_open || self.selected_announce().is_none() {
             return;
         }
 
@@ -318,3 +609,136 @@ impl Widget for &mut DiscoveryView {
         self.render_modal(area, buf);
     }
 }
+
+/// "just now", "5m ago", "3h ago", "2d ago".
+pub(super) fn format_ago(secs: i64) -> String {
+    match secs.max(0) {
+        0..60 => "just now".to_string(),
+        s @ 60..3600 => format!("{}m ago", s / 60),
+        s @ 3600..86400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86400),
+    }
+}
+
+/// The active filter, shown along the bottom border.
+pub(super) fn filter_title(filter: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(" / ", Style::default().fg(Color::Magenta)),
+        Span::styled(format!("{} ", filter), Style::default().fg(Color::White)),
+    ])
+}
+
+fn badge_color(kind: AnnounceKind) -> Color {
+    match kind {
+        AnnounceKind::Node => Color::Magenta,
+        AnnounceKind::Peer => Color::Green,
+        AnnounceKind::PropagationNode => Color::Cyan,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn announce(byte: u8, kind: AnnounceKind, name: Option<&str>) -> Announce {
+        Announce {
+            hash: [byte; 16],
+            kind,
+            name: name.map(str::to_string),
+            first_seen: 0,
+            last_seen: byte as i64,
+            announce_count: 1,
+            hops: None,
+            interface: None,
+        }
+    }
+
+    #[test]
+    fn test_sections_and_filter_keep_selection() {
+        let mut view = DiscoveryView::new();
+        view.add(announce(1, AnnounceKind::Peer, Some("alice")));
+        view.add(announce(2, AnnounceKind::Node, None));
+        view.add(announce(3, AnnounceKind::PropagationNode, None));
+        assert_eq!(
+            view.rows(),
+            [
+                Row::Header(AnnounceKind::Node, 1),
+                Row::Entry(0),
+                Row::Header(AnnounceKind::Peer, 1),
+                Row::Entry(1),
+                Row::Header(AnnounceKind::PropagationNode, 1),
+                Row::Entry(2),
+            ]
+        );
+
+        // The first entry stays selected as others are sorted in above it.
+        assert_eq!(view.selected_announce().unwrap().hash, [1; 16]);
+        view.add(announce(2, AnnounceKind::Node, Some("library")));
+        assert_eq!(view.nodes()[0].name, "library");
+
+        view.cycle_kind_filter();
+        assert_eq!(view.kind_filter(), Some(AnnounceKind::Node));
+        assert_eq!(view.selected_announce().unwrap().hash, [2; 16]);
+        view.cycle_kind_filter();
+        assert_eq!(view.selected_announce().unwrap().hash, [1; 16]);
+        assert!(view.selected_node().is_none());
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let mut view = DiscoveryView::new();
+        let mut far = announce(1, AnnounceKind::Node, Some("zeta"));
+        far.hops = Some(4);
+        let mut near = announce(2, AnnounceKind::Node, Some("alpha"));
+        near.hops = Some(1);
+        view.add(far);
+        view.add(near);
+        view.add(announce(3, AnnounceKind::Node, None));
+        let order = |view: &DiscoveryView| -> Vec<u8> {
+            view.visible().iter().map(|a| a.hash[0]).collect()
+        };
+
+        assert_eq!(order(&view), [3, 2, 1]);
+        view.cycle_sort();
+        assert_eq!(view.sort(), SortOrder::Name);
+        assert_eq!(order(&view), [2, 1, 3]);
+        view.cycle_sort();
+        assert_eq!(order(&view), [2, 1, 3]);
+        assert_eq!(view.selected_announce().unwrap().hash, [1; 16]);
+    }
+
+    #[test]
+    fn test_filter_keeps_selection_and_ranks_matches() {
+        let mut view = DiscoveryView::new();
+        view.add(announce(1, AnnounceKind::Node, Some("mesh library")));
+        view.add(announce(2, AnnounceKind::Node, Some("librarian")));
+        view.add(announce(3, AnnounceKind::Peer, Some("alice")));
+        assert_eq!(view.selected_announce().unwrap().hash, [1; 16]);
+
+        view.set_filter("l");
+        view.set_filter("lib");
+        assert_eq!(view.selected_announce().unwrap().hash, [1; 16]);
+        assert_eq!(
+            view.rows(),
+            [
+                Row::Header(AnnounceKind::Node, 2),
+                Row::Entry(0),
+                Row::Entry(1)
+            ]
+        );
+        assert_eq!(view.visible()[0].hash, [2; 16]);
+
+        view.set_filter("0303");
+        assert_eq!(view.selected_announce().unwrap().hash, [3; 16]);
+        view.set_filter("");
+        assert_eq!(view.visible().len(), 3);
+    }
+
+    #[test]
+    fn test_format_ago() {
+        assert_eq!(format_ago(-5), "just now");
+        assert_eq!(format_ago(300), "5m ago");
+        assert_eq!(format_ago(7200), "2h ago");
+        assert_eq!(format_ago(200_000), "2d ago");
+    }
+}
@@ -0,0 +1,476 @@
+use crate::network::NodeInfo;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How often the transfer speed is resampled.
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownloadState {
+    Queued,
+    Active,
+    Done { path: String },
+    Failed { reason: String },
+    Cancelled,
+}
+
+impl DownloadState {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, DownloadState::Queued | DownloadState::Active)
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            DownloadState::Queued => Color::DarkGray,
+            DownloadState::Active => Color::Cyan,
+            DownloadState::Done { .. } => Color::Green,
+            DownloadState::Failed { .. } => Color::Red,
+            DownloadState::Cancelled => Color::Yellow,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadAction {
+    Cancel,
+    Retry,
+    OpenFolder,
+}
+
+#[derive(Debug, Clone)]
+pub struct Download {
+    pub transfer_id: u64,
+    pub node: NodeInfo,
+    pub path: String,
+    pub filename: String,
+    pub dir: PathBuf,
+    pub state: DownloadState,
+    received_bytes: usize,
+    total_bytes: usize,
+    /// Bytes already on disk from an earlier attempt.
+    resumed_from: u64,
+    last_sample: Option<(Instant, usize)>,
+    bytes_per_sec: f64,
+}
+
+impl Download {
+    fn new(transfer_id: u64, node: NodeInfo, path: String, filename: String, dir: PathBuf) -> Self {
+        Self {
+            transfer_id,
+            node,
+            path,
+            filename,
+            dir,
+            state: DownloadState::Queued,
+            received_bytes: 0,
+            total_bytes: 0,
+            resumed_from: 0,
+            last_sample: None,
+            bytes_per_sec: 0.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = DownloadState::Queued;
+        self.received_bytes = 0;
+        self.total_bytes = 0;
+        self.resumed_from = 0;
+        self.last_sample = None;
+        self.bytes_per_sec = 0.0;
+    }
+
+    fn update_progress(&mut self, received_bytes: usize, total_bytes: usize) {
+        let now = Instant::now();
+        match self.last_sample {
+            Some((at, bytes)) if now.duration_since(at) >= SPEED_SAMPLE_INTERVAL => {
+                let rate = received_bytes.saturating_sub(bytes) as f64
+                    / now.duration_since(at).as_secs_f64();
+                // Smooth out bursty resource segments.
+                self.bytes_per_sec = if self.bytes_per_sec == 0.0 {
+                    rate
+                } else {
+                    self.bytes_per_sec * 0.7 + rate * 0.3
+                };
+                self.last_sample = Some((now, received_bytes));
+            }
+            Some(_) => {}
+            None => self.last_sample = Some((now, received_bytes)),
+        }
+        self.state = DownloadState::Active;
+        self.received_bytes = received_bytes;
+        self.total_bytes = total_bytes;
+    }
+
+    fn eta(&self) -> Option<Duration> {
+        if self.bytes_per_sec <= 0.0 || self.total_bytes == 0 {
+            return None;
+        }
+        let remaining = self.total_bytes.saturating_sub(self.received_bytes) as f64;
+        Some(Duration::from_secs_f64(remaining / self.bytes_per_sec))
+    }
+
+    fn action(&self) -> DownloadAction {
+        match self.state {
+            DownloadState::Queued | DownloadState::Active => DownloadAction::Cancel,
+            DownloadState::Done { .. } => DownloadAction::OpenFolder,
+            DownloadState::Failed { .. } | DownloadState::Cancelled => DownloadAction::Retry,
+        }
+    }
+}
+
+pub struct DownloadsView {
+    downloads: Vec<Download>,
+    selected: usize,
+    offset: usize,
+    last_row_areas: Vec<(usize, Rect)>,
+    last_button_areas: Vec<(usize, Rect)>,
+}
+
+impl Default for DownloadsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DownloadsView {
+    pub fn new() -> Self {
+        Self {
+            downloads: Vec::new(),
+            selected: 0,
+            offset: 0,
+            last_row_areas: Vec::new(),
+            last_button_areas: Vec::new(),
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        transfer_id: u64,
+        node: NodeInfo,
+        path: String,
+        filename: String,
+        dir: PathBuf,
+    ) {
+        self.downloads
+            .push(Download::new(transfer_id, node, path, filename, dir));
+    }
+
+    pub fn get(&self, transfer_id: u64) -> Option<&Download> {
+        self.downloads.iter().find(|d| d.transfer_id == transfer_id)
+    }
+
+    fn get_mut(&mut self, transfer_id: u64) -> Option<&mut Download> {
+        self.downloads
+            .iter_mut()
+            .find(|d| d.transfer_id == transfer_id)
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.downloads
+            .iter()
+            .filter(|d| !d.state.is_finished())
+            .count()
+    }
+
+    pub fn mark_started(&mut self, transfer_id: u64, resumed_from: u64) {
+        if let Some(download) = self.get_mut(transfer_id) {
+            download.reset();
+            download.state = DownloadState::Active;
+            download.resumed_from = resumed_from;
+        }
+    }
+
+    pub fn update_progress(&mut self, transfer_id: u64, received_bytes: usize, total_bytes: usize) {
+        if let Some(download) = self.get_mut(transfer_id) {
+            if download.state == DownloadState::Active {
+                download.update_progress(received_bytes, total_bytes);
+            }
+        }
+    }
+
+    pub fn mark_done(&mut self, transfer_id: u64, path: String) {
+        if let Some(download) = self.get_mut(transfer_id) {
+            download.received_bytes = download.total_bytes;
+            download.state = DownloadState::Done { path };
+        }
+    }
+
+    /// Records a failure unless the user already cancelled the transfer.
+    pub fn mark_failed(&mut self, transfer_id: u64, reason: String) {
+        if let Some(download) = self.get_mut(transfer_id) {
+            if download.state != DownloadState::Cancelled {
+                download.state = DownloadState::Failed { reason };
+            }
+        }
+    }
+
+    pub fn mark_cancelled(&mut self, transfer_id: u64) {
+        if let Some(download) = self.get_mut(transfer_id) {
+            download.state = DownloadState::Cancelled;
+        }
+    }
+
+    /// Puts a failed or cancelled download back in the queue.
+    pub fn retry(&mut self, transfer_id: u64) -> Option<&Download> {
+        let download = self.get_mut(transfer_id)?;
+        download.reset();
+        Some(download)
+    }
+
+    pub fn selected(&self) -> Option<&Download> {
+        self.downloads.get(self.selected)
+    }
+
+    pub fn selected_action(&self) -> Option<(u64, DownloadAction)> {
+        self.selected().map(|d| (d.transfer_id, d.action()))
+    }
+
+    /// Removes the selected download from the list once it has finished.
+    pub fn remove_selected(&mut self) -> Option<Download> {
+        if !self.selected().is_some_and(|d| d.state.is_finished()) {
+            return None;
+        }
+        let removed = self.downloads.remove(self.selected);
+        if self.selected >= self.downloads.len() && !self.downloads.is_empty() {
+            self.selected = self.downloads.len() - 1;
+        }
+        Some(removed)
+    }
+
+    pub fn scroll_down(&mut self) {
+        if !self.downloads.is_empty() {
+            self.selected = (self.selected + 1) % self.downloads.len();
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        if !self.downloads.is_empty() {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.downloads.len() - 1);
+        }
+    }
+
+    /// Selects the clicked row, returning the action if a button was hit.
+    pub fn click(&mut self, x: u16, y: u16) -> Option<(u64, DownloadAction)> {
+        if let Some((idx, _)) = self
+            .last_button_areas
+            .iter()
+            .find(|(_, area)| area.contains((x, y).into()))
+        {
+            self.selected = *idx;
+            return self.selected_action();
+        }
+        if let Some((idx, _)) = self
+            .last_row_areas
+            .iter()
+            .find(|(_, area)| area.contains((x, y).into()))
+        {
+            self.selected = *idx;
+        }
+        None
+    }
+}
+
+impl Widget for &mut DownloadsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.last_row_areas.clear();
+        self.last_button_areas.clear();
+
+        let block = Block::default()
+            .title(Line::from(vec![
+                Span::styled(
+                    " Downloads ",
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("({} active) ", self.active_count()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.downloads.is_empty() {
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "No downloads yet",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(inner, buf);
+            return;
+        }
+
+        let row_height = 4u16;
+        let max_rows = ((inner.height / row_height) as usize).max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + max_rows {
+            self.offset = self.selected + 1 - max_rows;
+        }
+
+        let rows: Vec<_> = self
+            .downloads
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(max_rows)
+            .map(|(idx, d)| (idx, d.clone()))
+            .collect();
+
+        for (i, (idx, download)) in rows.into_iter().enumerate() {
+            let y = inner.y + (i as u16) * row_height;
+            let row_area = Rect::new(inner.x, y, inner.width, row_height.min(inner.height));
+            self.render_download_row(idx, &download, row_area, buf);
+        }
+    }
+}
+
+impl DownloadsView {
+    fn render_download_row(
+        &mut self,
+        idx: usize,
+        download: &Download,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        self.last_row_areas.push((idx, area));
+
+        let border_color = if idx == self.selected {
+            Color::Cyan
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let chunks = Layout::horizontal([Constraint::Min(20), Constraint::Length(15)]).split(inner);
+
+        let status_line = match &download.state {
+            DownloadState::Queued => Line::from(Span::styled(
+                "  Queued",
+                Style::default().fg(Color::DarkGray),
+            )),
+            DownloadState::Active if download.total_bytes == 0 => Line::from(Span::styled(
+                "  Waiting for data...",
+                Style::default().fg(Color::DarkGray),
+            )),
+            DownloadState::Active => {
+                let fraction = download.received_bytes as f64 / download.total_bytes as f64;
+                let mut spans = vec![
+                    Span::raw("  "),
+                    Span::styled(progress_bar(20, fraction), Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        format!(
+                            " {:>3}%  {} / {}",
+                            (fraction * 100.0) as u64,
+                            super::format_bytes(download.received_bytes as u64),
+                            super::format_bytes(download.total_bytes as u64)
+                        ),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ];
+                if download.bytes_per_sec > 0.0 {
+                    spans.push(Span::styled(
+                        format!("  {}/s", super::format_bytes(download.bytes_per_sec as u64)),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                if let Some(eta) = download.eta() {
+                    spans.push(Span::styled(
+                        format!("  ETA {}", super::format_uptime(eta.as_secs())),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                if download.resumed_from > 0 {
+                    spans.push(Span::styled(
+                        format!(
+                            "  resumed at {}",
+                            super::format_bytes(download.resumed_from)
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Line::from(spans)
+            }
+            DownloadState::Done { path } => Line::from(vec![
+                Span::styled("  Saved to ", Style::default().fg(Color::DarkGray)),
+                Span::styled(path.clone(), Style::default().fg(Color::Green)),
+            ]),
+            DownloadState::Failed { reason } => Line::from(Span::styled(
+                format!("  Failed: {}", reason),
+                Style::default().fg(Color::Red),
+            )),
+            DownloadState::Cancelled => Line::from(Span::styled(
+                "  Cancelled",
+                Style::default().fg(Color::Yellow),
+            )),
+        };
+
+        let info_lines = vec![
+            Line::from(vec![
+                Span::styled("\u{25CF} ", Style::default().fg(download.state.color())),
+                Span::styled(
+                    download.filename.clone(),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  from {}", download.node.name),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            status_line,
+        ];
+        Paragraph::new(info_lines).render(chunks[0], buf);
+
+        let (button_text, button_color) = match download.action() {
+            DownloadAction::Cancel => (" Cancel ", Color::Red),
+            DownloadAction::Retry => (" Retry ", Color::Yellow),
+            DownloadAction::OpenFolder => (" Open Folder ", Color::Cyan),
+        };
+        let button_width = button_text.len() as u16;
+        let button_x = chunks[1].x + chunks[1].width.saturating_sub(button_width) / 2;
+        let button_y = chunks[1].y;
+        buf.set_string(
+            button_x,
+            button_y,
+            button_text,
+            Style::default()
+                .fg(Color::Black)
+                .bg(button_color)
+                .add_modifier(Modifier::BOLD),
+        );
+        self.last_button_areas
+            .push((idx, Rect::new(button_x, button_y, button_width, 1)));
+    }
+}
+
+fn progress_bar(width: usize, fraction: f64) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!(
+        "{}{}",
+        "\u{2588}".repeat(filled),
+        "\u{2591}".repeat(width - filled)
+    )
+}
@@ -0,0 +1,210 @@
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Paragraph, widgets::Widget};
+
+/// Stop capturing after this many rows so a runaway page can't stall the UI.
+const MAX_CAPTURED_ROWS: usize = 20_000;
+
+/// One rendered row, lowercased, with the buffer column of every char so a
+/// match can be mapped back onto screen cells.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowText {
+    chars: Vec<char>,
+    columns: Vec<u16>,
+}
+
+impl RowText {
+    fn from_buffer(buf: &Buffer, area: Rect, y: u16) -> Self {
+        let mut row = RowText::default();
+        for x in area.x..area.x + area.width {
+            for c in buf[(x, y)].symbol().chars().flat_map(char::to_lowercase) {
+                row.chars.push(c);
+                row.columns.push(x - area.x);
+            }
+        }
+        row
+    }
+
+    fn is_blank(&self) -> bool {
+        self.chars.iter().all(|c| c.is_whitespace())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FindMatch {
+    pub row: usize,
+    pub start: u16,
+    pub end: u16,
+}
+
+/// Renders the whole page off screen one viewport at a time and captures the
+/// text of every row.
+pub fn capture_page(paragraph: &Paragraph, width: u16, page_height: u16) -> Vec<RowText> {
+    let area = Rect::new(0, 0, width, page_height.max(1));
+    let total = paragraph.line_count(width).min(MAX_CAPTURED_ROWS);
+    let mut rows = Vec::with_capacity(total);
+
+    while rows.len() < total {
+        let Ok(offset) = u16::try_from(rows.len()) else {
+            break;
+        };
+        let mut buf = Buffer::empty(area);
+        paragraph.clone().scroll((offset, 0)).render(area, &mut buf);
+        let remaining = (total - rows.len()).min(area.height as usize) as u16;
+        rows.extend((0..remaining).map(|y| RowText::from_buffer(&buf, area, y)));
+    }
+    rows
+}
+
+pub fn capture_viewport(buf: &Buffer, area: Rect) -> Vec<RowText> {
+    (area.y..area.y + area.height)
+        .map(|y| RowText::from_buffer(buf, area, y))
+        .collect()
+}
+
+/// Case-insensitive, non-overlapping matches in page order.
+pub fn find_matches(rows: &[RowText], query: &str) -> Vec<FindMatch> {
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for (row_idx, row) in rows.iter().enumerate() {
+        let mut i = 0;
+        while i + needle.len() <= row.chars.len() {
+            if row.chars[i..i + needle.len()] == needle[..] {
+                matches.push(FindMatch {
+                    row: row_idx,
+                    start: row.columns[i],
+                    end: row.columns[i + needle.len() - 1] + 1,
+                });
+                i += needle.len();
+            } else {
+                i += 1;
+            }
+        }
+    }
+    matches
+}
+
+/// Whether `visible`, captured from the screen, shows `rows` from `top` down,
+/// i.e. the captured page still matches what is on screen.
+pub fn viewport_matches(rows: &[RowText], visible: &[RowText], top: usize) -> bool {
+    visible
+        .iter()
+        .enumerate()
+        .all(|(i, v)| match rows.get(top + i) {
+            Some(row) => row == v,
+            None => v.is_blank(),
+        })
+}
+
+#[derive(Debug, Default)]
+pub struct FindState {
+    pub query: String,
+    pub rows: Vec<RowText>,
+    pub width: u16,
+    pub url: Option<String>,
+    pub matches: Vec<FindMatch>,
+    pub current: usize,
+}
+
+impl FindState {
+    pub fn current_match(&self) -> Option<FindMatch> {
+        self.matches.get(self.current).copied()
+    }
+
+    /// The first match at or below `top`, wrapping to the first one.
+    pub fn select_from(&mut self, top: usize) {
+        self.current = self.matches.iter().position(|m| m.row >= top).unwrap_or(0);
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = self
+                .current
+                .checked_sub(1)
+                .unwrap_or(self.matches.len() - 1);
+        }
+    }
+
+    pub fn position_label(&self) -> String {
+        if self.matches.is_empty() {
+            "0/0".to_string()
+        } else {
+            format!("{}/{}", self.current + 1, self.matches.len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::text::Line;
+
+    #[test]
+    fn test_capture_and_find_across_viewports() {
+        // A blank stretch longer than the viewport mustn't end the capture.
+        let lines: Vec<Line> = (0..25)
+            .map(|i| Line::from(format!("post {} by Alice", i)))
+            .chain((0..12).map(|_| Line::from("")))
+            .chain([Line::from("footer")])
+            .collect();
+        let paragraph = Paragraph::new(lines);
+
+        let rows = capture_page(&paragraph, 30, 10);
+        assert_eq!(rows.len(), 38);
+        assert_eq!(find_matches(&rows, "footer")[0].row, 37);
+
+        let matches = find_matches(&rows, "alice");
+        assert_eq!(matches.len(), 25);
+        assert_eq!(
+            matches[12],
+            FindMatch {
+                row: 12,
+                start: 11,
+                end: 16
+            }
+        );
+        assert_eq!(find_matches(&rows, "post 2").len(), 6);
+        assert!(find_matches(&rows, "").is_empty());
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 10));
+        paragraph.clone().scroll((14, 0)).render(buf.area, &mut buf);
+        let visible = capture_viewport(&buf, buf.area);
+        assert!(viewport_matches(&rows, &visible, 14));
+        assert!(!viewport_matches(&rows, &visible, 13));
+    }
+
+    #[test]
+    fn test_select_wraps() {
+        let mut state = FindState {
+            matches: vec![
+                FindMatch {
+                    row: 3,
+                    start: 0,
+                    end: 1,
+                },
+                FindMatch {
+                    row: 40,
+                    start: 0,
+                    end: 1,
+                },
+            ],
+            ..Default::default()
+        };
+        state.select_from(10);
+        assert_eq!(state.position_label(), "2/2");
+        state.select_next();
+        assert_eq!(state.position_label(), "1/2");
+        state.select_prev();
+        assert_eq!(state.position_label(), "2/2");
+        state.select_from(50);
+        assert_eq!(state.position_label(), "1/2");
+    }
+}
@@ -0,0 +1,50 @@
+/// How well `query` matches a destination, higher is better. A hash prefix
+/// beats a name prefix, which beats a name substring, which beats the
+/// query's characters appearing in order anywhere in the name.
+pub fn score(query: &str, name: &str, hash_hex: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Some(0);
+    }
+    if hash_hex.starts_with(&query) {
+        return Some(1000);
+    }
+
+    let name = name.to_lowercase();
+    if name.starts_with(&query) {
+        return Some(900);
+    }
+    if let Some(pos) = name.find(&query) {
+        return Some(800 - pos.min(100) as u32);
+    }
+
+    // Subsequence match, penalised by how spread out it is.
+    let mut gaps = 0;
+    let mut last = None;
+    let mut chars = name.char_indices();
+    for q in query.chars() {
+        let (idx, _) = chars.by_ref().find(|&(_, c)| c == q)?;
+        if let Some(last) = last {
+            gaps += idx - last - 1;
+        }
+        last = Some(idx);
+    }
+    Some(500u32.saturating_sub(gaps as u32 * 10).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_ranking() {
+        let hash = "ab12cd34ef56ab12cd34ef56ab12cd34";
+        assert_eq!(score("", "Library", hash), Some(0));
+        assert_eq!(score("ab12", "Library", hash), Some(1000));
+        assert!(score("lib", "Library", hash) > score("rary", "Library", hash));
+        assert!(score("rary", "Library", hash) > score("lbr", "Library", hash));
+        assert!(score("lby", "Library", hash) < score("lbr", "Library", hash));
+        assert_eq!(score("xyz", "Library", hash), None);
+        assert_eq!(score("cd34", "Library", hash), None);
+    }
+}
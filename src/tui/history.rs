@@ -0,0 +1,433 @@
+use crate::history::HistoryEntry;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Widget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    None,
+    Open,
+    FilterNode,
+}
+
+pub struct HistoryView {
+    entries: Vec<HistoryEntry>,
+    visible: Vec<usize>,
+    filter: String,
+    node_filter: Option<String>,
+    list_state: ListState,
+    last_list_area: Rect,
+    open_button_area: Option<Rect>,
+    node_button_area: Option<Rect>,
+}
+
+impl Default for HistoryView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistoryView {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            visible: Vec::new(),
+            filter: String::new(),
+            node_filter: None,
+            list_state: ListState::default(),
+            last_list_area: Rect::default(),
+            open_button_area: None,
+            node_button_area: None,
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<HistoryEntry>) {
+        self.entries = entries;
+        self.refilter();
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        let selected = self.selected_index();
+        self.entries.push(entry);
+        self.refilter();
+        // Keep the highlighted entry in place as new visits land on top.
+        if let Some(idx) = selected {
+            self.select_index(idx);
+        }
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: &str) {
+        if self.filter != filter {
+            self.filter = filter.to_string();
+            self.refilter();
+            self.list_state.select(Some(0));
+        }
+    }
+
+    /// Restricts the list to the selected entry's node, or lifts that
+    /// restriction if it is already in place.
+    pub fn toggle_node_filter(&mut self) {
+        if self.node_filter.is_some() {
+            self.node_filter = None;
+        } else if let Some(entry) = self.selected_entry() {
+            self.node_filter = Some(node_key(entry).to_string());
+        }
+        let selected = self.selected_index();
+        self.refilter();
+        match selected {
+            Some(idx) => self.select_index(idx),
+            None => self.list_state.select(Some(0)),
+        }
+    }
+
+    pub fn node_filter_name(&self) -> Option<&str> {
+        let key = self.node_filter.as_deref()?;
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| node_key(e) == key)
+            .map(|e| e.node_name.as_str())
+    }
+
+    pub fn selected_entry(&self) -> Option<&HistoryEntry> {
+        self.selected_index().map(|idx| &self.entries[idx])
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.visible
+            .get(self.list_state.selected().unwrap_or(0))
+            .copied()
+    }
+
+    fn select_index(&mut self, idx: usize) {
+        let pos = self.visible.iter().position(|&i| i == idx).unwrap_or(0);
+        self.list_state.select(Some(pos));
+    }
+
+    fn refilter(&mut self) {
+        let needle = self.filter.to_lowercase();
+        self.visible = self
+            .entries
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, e)| {
+                self.node_filter
+                    .as_deref()
+                    .is_none_or(|key| node_key(e) == key)
+            })
+            .filter(|(_, e)| needle.is_empty() || matches_text(e, &needle))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if self.visible.is_empty() {
+            self.list_state.select(None);
+        } else if self
+            .list_state
+            .selected()
+            .is_none_or(|s| s >= self.visible.len())
+        {
+            self.list_state.select(Some(0));
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let i = match self.list_state.selected() {
+            Some(i) => (i + 1) % self.visible.len(),
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let i = match self.list_state.selected() {
+            Some(i) => i.checked_sub(1).unwrap_or(self.visible.len() - 1),
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.list_state.scroll_up_by(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.list_state.scroll_down_by(1);
+    }
+
+    pub fn click(&mut self, x: u16, y: u16) -> Option<usize> {
+        let list_inner = Rect::new(
+            self.last_list_area.x + 1,
+            self.last_list_area.y + 2,
+            self.last_list_area.width.saturating_sub(2),
+            self.last_list_area.height.saturating_sub(3),
+        );
+
+        if !list_inner.contains((x, y).into()) {
+            return None;
+        }
+
+        let idx = self.list_state.offset() + y.saturating_sub(list_inner.y) as usize;
+        if idx < self.visible.len() {
+            self.list_state.select(Some(idx));
+            Some(idx)
+        } else {
+            None
+        }
+    }
+
+    pub fn click_detail(&self, x: u16, y: u16) -> HistoryAction {
+        let point = Rect::new(x, y, 1, 1);
+        if self.open_button_area.is_some_and(|a| a.intersects(point)) {
+            HistoryAction::Open
+        } else if self.node_button_area.is_some_and(|a| a.intersects(point)) {
+            HistoryAction::FilterNode
+        } else {
+            HistoryAction::None
+        }
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        self.last_list_area = area;
+
+        let mut title = vec![
+            Span::styled(
+                " History ",
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("({}) ", self.visible.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        if let Some(name) = self.node_filter_name() {
+            title.push(Span::styled(
+                format!("[{}] ", name),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+
+        let block = Block::default()
+            .title(Line::from(title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height == 0 {
+            return;
+        }
+
+        let filter_line = if self.filter.is_empty() {
+            Line::from(Span::styled(
+                " / to filter",
+                Style::default().fg(Color::DarkGray),
+            ))
+        } else {
+            Line::from(vec![
+                Span::styled(" / ", Style::default().fg(Color::Magenta)),
+                Span::styled(&self.filter, Style::default().fg(Color::White)),
+            ])
+        };
+        Paragraph::new(filter_line).render(Rect::new(inner.x, inner.y, inner.width, 1), buf);
+
+        let list_area = Rect::new(
+            inner.x,
+            inner.y + 1,
+            inner.width,
+            inner.height.saturating_sub(1),
+        );
+
+        if self.visible.is_empty() {
+            let message = if self.entries.is_empty() {
+                "Pages you visit will show up here"
+            } else {
+                "No matching pages"
+            };
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(message, Style::default().fg(Color::DarkGray))),
+            ])
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(list_area, buf);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|&idx| {
+                let entry = &self.entries[idx];
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {}  ", format_visit_time(entry.visited_at)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(display_title(entry), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("  {}", entry.node_name),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("");
+
+        ratatui::widgets::StatefulWidget::render(list, list_area, buf, &mut self.list_state);
+    }
+
+    fn render_detail(&mut self, area: Rect, buf: &mut Buffer) {
+        self.open_button_area = None;
+        self.node_button_area = None;
+
+        let block = Block::default()
+            .title(Line::from(vec![Span::styled(
+                " Page Info ",
+                Style::default().fg(Color::White),
+            )]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(entry) = self.selected_entry() else {
+            Paragraph::new(Line::from(Span::styled(
+                "Select a page to view details",
+                Style::default().fg(Color::DarkGray),
+            )))
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(inner, buf);
+            return;
+        };
+
+        let content = vec![
+            Line::from(Span::styled(
+                display_title(entry),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Node: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(&entry.node_name, Style::default().fg(Color::Gray)),
+            ]),
+            Line::from(vec![
+                Span::styled("Visited: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format_visit_time(entry.visited_at),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("URL:", Style::default().fg(Color::DarkGray))),
+            Line::from(Span::styled(&entry.url, Style::default().fg(Color::Cyan))),
+        ];
+
+        Paragraph::new(content)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .render(inner, buf);
+
+        let button_y = inner.y + inner.height.saturating_sub(1);
+        let mut x = inner.x;
+
+        let node_text = if self.node_filter.is_some() {
+            " All Nodes "
+        } else {
+            " This Node "
+        };
+        buf.set_string(
+            x,
+            button_y,
+            node_text,
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        );
+        self.node_button_area = Some(Rect::new(x, button_y, node_text.len() as u16, 1));
+        x += node_text.len() as u16 + 1;
+
+        let open_text = " Open ";
+        buf.set_string(
+            x,
+            button_y,
+            open_text,
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        );
+        self.open_button_area = Some(Rect::new(x, button_y, open_text.len() as u16, 1));
+    }
+}
+
+impl Widget for &mut HistoryView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let chunks = ratatui::layout::Layout::horizontal([
+            ratatui::layout::Constraint::Percentage(60),
+            ratatui::layout::Constraint::Percentage(40),
+        ])
+        .split(area);
+
+        self.render_list(chunks[0], buf);
+        self.render_detail(chunks[1], buf);
+    }
+}
+
+fn node_key(entry: &HistoryEntry) -> &str {
+    entry.url.split(':').next().unwrap_or("")
+}
+
+fn matches_text(entry: &HistoryEntry, needle: &str) -> bool {
+    entry.node_name.to_lowercase().contains(needle)
+        || entry.url.to_lowercase().contains(needle)
+        || entry
+            .title
+            .as_deref()
+            .is_some_and(|t| t.to_lowercase().contains(needle))
+}
+
+fn display_title(entry: &HistoryEntry) -> String {
+    match &entry.title {
+        Some(title) => title.clone(),
+        None => entry
+            .url
+            .split_once(':')
+            .map(|(_, path)| path.to_string())
+            .unwrap_or_else(|| entry.url.clone()),
+    }
+}
+
+fn format_visit_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
@@ -5,16 +5,37 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
+use rinse::config::InterfaceConfig;
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::time::Instant;
+
+use super::discovery::format_ago;
+use crate::network::supervisor::Retry;
+use crate::network::traffic::InterfaceStats;
 
 #[derive(Debug, Clone)]
 pub struct InterfaceInfo {
     pub name: String,
     pub kind: InterfaceKind,
     pub address: String,
+    pub enabled: bool,
+    /// For clients, whether the link is up; for servers, whether the listen
+    /// address is bound.
     pub connected: bool,
+    /// Peers connected to a server, as `ip:port`.
+    pub clients: Vec<String>,
+    /// When a dropped client link is next retried.
+    pub retry: Option<Retry>,
+    pub stats: InterfaceStats,
 }
 
-#[derive(Debug, Clone)]
+/// Peer addresses listed under a server before the rest are summarised.
+const MAX_LISTED_CLIENTS: usize = 4;
+/// Seconds of throughput kept for the detail pane's sparkline.
+const RATE_HISTORY: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InterfaceKind {
     TcpClient,
     TcpServer,
@@ -36,10 +57,172 @@ impl InterfaceKind {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormField {
+    Name,
+    Host,
+    Port,
+}
+
+impl FormField {
+    pub fn next(self) -> Self {
+        match self {
+            FormField::Name => FormField::Host,
+            FormField::Host => FormField::Port,
+            FormField::Port => FormField::Name,
+        }
+    }
+}
+
+/// An interface being added or edited. While the form is open the focused
+/// field's text lives in `TuiApp::input`.
+#[derive(Debug, Clone)]
+pub struct InterfaceForm {
+    /// The interface's name before editing, `None` when adding one.
+    pub original: Option<String>,
+    pub kind: InterfaceKind,
+    pub enabled: bool,
+    pub name: String,
+    pub host: String,
+    pub port: String,
+    pub focus: FormField,
+}
+
+impl InterfaceForm {
+    pub fn new(kind: InterfaceKind) -> Self {
+        let host = match kind {
+            InterfaceKind::TcpClient => "",
+            InterfaceKind::TcpServer => "0.0.0.0",
+        };
+        Self {
+            original: None,
+            kind,
+            enabled: true,
+            name: String::new(),
+            host: host.to_string(),
+            port: "4242".to_string(),
+            focus: FormField::Name,
+        }
+    }
+
+    pub fn edit(iface: &InterfaceInfo) -> Self {
+        let (host, port) = iface
+            .address
+            .rsplit_once(':')
+            .unwrap_or((&iface.address, ""));
+        Self {
+            original: Some(iface.name.clone()),
+            kind: iface.kind,
+            enabled: iface.enabled,
+            name: iface.name.clone(),
+            host: host.to_string(),
+            port: port.to_string(),
+            focus: FormField::Name,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        let verb = if self.original.is_some() {
+            "Edit"
+        } else {
+            "Add"
+        };
+        format!("{} {} Interface", verb, self.kind.label())
+    }
+
+    pub fn label(&self, field: FormField) -> &'static str {
+        match (field, self.kind) {
+            (FormField::Name, _) => "  Name      ",
+            (FormField::Host, InterfaceKind::TcpClient) => "  Host      ",
+            (FormField::Host, InterfaceKind::TcpServer) => "  Listen IP ",
+            (FormField::Port, _) => "  Port      ",
+        }
+    }
+
+    pub fn field(&self, field: FormField) -> &str {
+        match field {
+            FormField::Name => &self.name,
+            FormField::Host => &self.host,
+            FormField::Port => &self.port,
+        }
+    }
+
+    pub fn set_field(&mut self, field: FormField, value: String) {
+        match field {
+            FormField::Name => self.name = value,
+            FormField::Host => self.host = value,
+            FormField::Port => self.port = value,
+        }
+    }
+
+    /// Checks the fields and builds the config to save. `taken` says whether
+    /// some other interface already uses a name.
+    pub fn to_config(
+        &self,
+        taken: impl Fn(&str) -> bool,
+    ) -> Result<(String, InterfaceConfig), String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Name can't be empty".into());
+        }
+        if self.original.as_deref() != Some(name) && taken(name) {
+            return Err(format!("There is already an interface called {}", name));
+        }
+
+        let host = self.host.trim().to_string();
+        match self.kind {
+            InterfaceKind::TcpClient if !is_valid_host(&host) => {
+                return Err(format!("{:?} isn't a valid host name or IP address", host));
+            }
+            InterfaceKind::TcpServer if host.parse::<IpAddr>().is_err() => {
+                return Err(format!("{:?} isn't an IP address to listen on", host));
+            }
+            _ => {}
+        }
+
+        let port = match self.port.trim().parse::<u16>() {
+            Ok(port) if port > 0 => port,
+            _ => return Err("Port must be a number from 1 to 65535".into()),
+        };
+
+        let config = match self.kind {
+            InterfaceKind::TcpClient => InterfaceConfig::TCPClientInterface {
+                enabled: self.enabled,
+                target_host: host,
+                target_port: port,
+            },
+            InterfaceKind::TcpServer => InterfaceConfig::TCPServerInterface {
+                enabled: self.enabled,
+                listen_ip: host,
+                listen_port: port,
+            },
+        };
+        Ok((name.to_string(), config))
+    }
+}
+
+/// An IP address or a DNS name made of letters, digits and inner hyphens.
+fn is_valid_host(host: &str) -> bool {
+    if host.parse::<IpAddr>().is_ok() {
+        return true;
+    }
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
 pub struct InterfacesView {
     interfaces: Vec<InterfaceInfo>,
     selected: usize,
     last_button_areas: Vec<(usize, Rect)>,
+    /// Bytes per second each way combined, by interface name.
+    rates: HashMap<String, VecDeque<u64>>,
 }
 
 impl InterfacesView {
@@ -48,19 +231,55 @@ impl InterfacesView {
             interfaces: Vec::new(),
             selected: 0,
             last_button_areas: Vec::new(),
+            rates: HashMap::new(),
         }
     }
 
+    /// Replaces the list, keeping the live state of interfaces that didn't
+    /// change; ones that were restarted report their state afresh.
     pub fn set_interfaces(&mut self, interfaces: Vec<InterfaceInfo>) {
-        self.interfaces = interfaces;
+        let previous = std::mem::replace(&mut self.interfaces, interfaces);
+        for iface in self.interfaces.iter_mut().filter(|i| i.enabled) {
+            if let Some(prev) = previous.iter().find(|p| {
+                p.name == iface.name && p.kind == iface.kind && p.address == iface.address
+            }) {
+                iface.connected = prev.connected;
+                iface.clients = prev.clients.clone();
+                iface.retry = prev.retry;
+                iface.stats = prev.stats.clone();
+            }
+        }
+        let interfaces = &self.interfaces;
+        self.rates
+            .retain(|name, _| interfaces.iter().any(|i| &i.name == name));
         if self.selected >= self.interfaces.len() && !self.interfaces.is_empty() {
             self.selected = self.interfaces.len() - 1;
         }
     }
 
-    pub fn update_status(&mut self, name: &str, connected: bool) {
+    pub fn update_status(&mut self, name: &str, connected: bool, retry: Option<Retry>) {
         if let Some(iface) = self.interfaces.iter_mut().find(|i| i.name == name) {
             iface.connected = connected;
+            iface.retry = retry;
+        }
+    }
+
+    pub fn update_traffic(&mut self, name: &str, stats: InterfaceStats) {
+        let Some(iface) = self.interfaces.iter_mut().find(|i| i.name == name) else {
+            return;
+        };
+        let total = |s: &InterfaceStats| s.bytes_in + s.bytes_out;
+        let rates = self.rates.entry(name.to_string()).or_default();
+        rates.push_back(total(&stats).saturating_sub(total(&iface.stats)));
+        if rates.len() > RATE_HISTORY {
+            rates.pop_front();
+        }
+        iface.stats = stats;
+    }
+
+    pub fn update_clients(&mut self, name: &str, clients: Vec<String>) {
+        if let Some(iface) = self.interfaces.iter_mut().find(|i| i.name == name) {
+            iface.clients = clients;
         }
     }
 
@@ -83,11 +302,15 @@ impl InterfacesView {
         self.interfaces.get(self.selected)
     }
 
+    pub fn has_interface(&self, name: &str) -> bool {
+        self.interfaces.iter().any(|i| i.name == name)
+    }
+
     pub fn click_reconnect(&self, x: u16, y: u16) -> Option<String> {
         for (idx, area) in &self.last_button_areas {
             if area.contains((x, y).into()) {
                 if let Some(iface) = self.interfaces.get(*idx) {
-                    if !iface.connected {
+                    if iface.enabled && !iface.connected {
                         return Some(iface.name.clone());
                     }
                 }
@@ -98,13 +321,23 @@ impl InterfacesView {
 
     pub fn try_reconnect_selected(&self) -> Option<String> {
         self.selected_interface()
-            .filter(|i| !i.connected)
+            .filter(|i| i.enabled && !i.connected)
             .map(|i| i.name.clone())
     }
 }
 
 impl Widget for &mut InterfacesView {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let chunks = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(area);
+
+        self.render_list(chunks[0], buf);
+        self.render_detail(chunks[1], buf);
+    }
+}
+
+impl InterfacesView {
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
         self.last_button_areas.clear();
 
         let block = Block::default()
@@ -135,7 +368,7 @@ impl Widget for &mut InterfacesView {
                 )),
                 Line::from(""),
                 Line::from(Span::styled(
-                    "Add interfaces in config.toml",
+                    "Press a to add a TCP client, A to add a TCP server",
                     Style::default().fg(Color::DarkGray),
                 )),
             ];
@@ -145,27 +378,103 @@ impl Widget for &mut InterfacesView {
             return;
         }
 
-        let row_height = 4u16;
         let selected = self.selected;
-        let max_rows = (inner.height / row_height) as usize;
-
-        let rows_to_render: Vec<_> = self
-            .interfaces
-            .iter()
-            .enumerate()
-            .take(max_rows)
-            .map(|(idx, iface)| (idx, iface.clone(), idx == selected))
-            .collect();
-
-        for (i, (idx, iface, is_selected)) in rows_to_render.into_iter().enumerate() {
-            let y = inner.y + (i as u16) * row_height;
+        let interfaces = self.interfaces.clone();
+        let mut y = inner.y;
+        for (idx, iface) in interfaces.iter().enumerate() {
+            let row_height = row_height(iface);
+            if y + row_height > inner.bottom() {
+                break;
+            }
             let row_area = Rect::new(inner.x, y, inner.width, row_height);
-            self.render_interface_row(idx, &iface, row_area, is_selected, buf);
+            self.render_interface_row(idx, iface, row_area, idx == selected, buf);
+            y += row_height;
         }
     }
-}
 
-impl InterfacesView {
+    fn render_detail(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(Line::from(vec![Span::styled(
+                " Traffic ",
+                Style::default().fg(Color::White),
+            )]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(iface) = self.selected_interface() else {
+            return;
+        };
+        let stats = &iface.stats;
+        let now = chrono::Utc::now().timestamp();
+        let row = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+                Span::styled(value, Style::default().fg(Color::White)),
+            ])
+        };
+        let direction = |bytes: u64, packets: u64| {
+            format!(
+                "{} \u{00B7} {} packets",
+                super::format_bytes(bytes),
+                packets
+            )
+        };
+
+        let chart_width = inner.width.saturating_sub(4) as usize;
+        let empty = VecDeque::new();
+        let rates = self.rates.get(&iface.name).unwrap_or(&empty);
+        let up_label = match iface.kind {
+            InterfaceKind::TcpClient => "  Connected  ",
+            InterfaceKind::TcpServer => "  Listening  ",
+        };
+        let up_for = match stats.up_since {
+            Some(since) => format!("for {}", super::format_uptime((now - since).max(0) as u64)),
+            None => "no".to_string(),
+        };
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(" {}", iface.name),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Throughput (last 60s):",
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    super::sparkline(rates, chart_width),
+                    Style::default().fg(iface.kind.color()),
+                ),
+            ]),
+            Line::from(""),
+            row("  In         ", direction(stats.bytes_in, stats.packets_in)),
+            row(
+                "  Out        ",
+                direction(stats.bytes_out, stats.packets_out),
+            ),
+            row("  Announces  ", format!("{} heard", stats.announces_heard)),
+            row(
+                "  Active     ",
+                stats
+                    .last_activity
+                    .map(|at| format_ago(now - at))
+                    .unwrap_or_else(|| "never".to_string()),
+            ),
+            row(up_label, up_for),
+        ];
+        if iface.kind == InterfaceKind::TcpClient {
+            lines.push(row("  Reconnects ", stats.reconnects.to_string()));
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
     fn render_interface_row(
         &mut self,
         idx: usize,
@@ -189,19 +498,28 @@ impl InterfacesView {
 
         let chunks = Layout::horizontal([Constraint::Min(20), Constraint::Length(14)]).split(inner);
 
-        let status_indicator = if iface.connected {
-            Span::styled("\u{25CF} ", Style::default().fg(Color::Green))
-        } else {
-            Span::styled("\u{25CF} ", Style::default().fg(Color::Red))
+        let status_color = match (iface.enabled, iface.connected) {
+            (false, _) => Color::DarkGray,
+            (true, true) => Color::Green,
+            (true, false) => Color::Red,
         };
-
-        let status_text = if iface.connected {
-            "Connected"
-        } else {
-            "Disconnected"
+        let status_indicator = Span::styled("\u{25CF} ", Style::default().fg(status_color));
+
+        let status_text = match (&iface.kind, iface.connected) {
+            _ if !iface.enabled => "Disabled".to_string(),
+            (InterfaceKind::TcpClient, true) => "Connected".to_string(),
+            (InterfaceKind::TcpClient, false) => match iface.retry {
+                Some(retry) => retry_text(retry),
+                None => "Disconnected".to_string(),
+            },
+            (InterfaceKind::TcpServer, true) => match iface.clients.len() {
+                1 => "Listening \u{00B7} 1 client".to_string(),
+                n => format!("Listening \u{00B7} {} clients", n),
+            },
+            (InterfaceKind::TcpServer, false) => "Not listening".to_string(),
         };
 
-        let info_lines = vec![
+        let mut info_lines = vec![
             Line::from(vec![
                 status_indicator,
                 Span::styled(
@@ -217,21 +535,32 @@ impl InterfacesView {
                 Span::styled(" \u{2192} ", Style::default().fg(Color::DarkGray)),
                 Span::styled(&iface.address, Style::default().fg(Color::Gray)),
                 Span::styled("  ", Style::default()),
-                Span::styled(
-                    status_text,
-                    Style::default().fg(if iface.connected {
-                        Color::Green
-                    } else {
-                        Color::Red
-                    }),
-                ),
+                Span::styled(status_text, Style::default().fg(status_color)),
             ]),
         ];
+        for client in iface.clients.iter().take(MAX_LISTED_CLIENTS) {
+            info_lines.push(Line::from(vec![
+                Span::styled("    \u{21b3} ", Style::default().fg(Color::DarkGray)),
+                Span::styled(client.as_str(), Style::default().fg(Color::Gray)),
+            ]));
+        }
+        if iface.clients.len() > MAX_LISTED_CLIENTS {
+            info_lines.push(Line::from(Span::styled(
+                format!(
+                    "    \u{2026} and {} more",
+                    iface.clients.len() - MAX_LISTED_CLIENTS
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
 
         Paragraph::new(info_lines).render(chunks[0], buf);
 
-        if !iface.connected {
-            let button_text = " Reconnect ";
+        if iface.enabled && !iface.connected {
+            let button_text = match iface.kind {
+                InterfaceKind::TcpClient => " Reconnect ",
+                InterfaceKind::TcpServer => " Listen ",
+            };
             let button_width = button_text.len() as u16;
             let button_x = chunks[1].x + (chunks[1].width.saturating_sub(button_width)) / 2;
             let button_y = chunks[1].y;
@@ -248,3 +577,68 @@ impl InterfacesView {
         }
     }
 }
+
+fn retry_text(retry: Retry) -> String {
+    let wait = retry.at.saturating_duration_since(Instant::now());
+    if wait.is_zero() {
+        format!("Reconnecting \u{00B7} attempt {}", retry.attempt + 1)
+    } else {
+        format!(
+            "Retrying in {}s \u{00B7} attempt {}",
+            wait.as_secs() + 1,
+            retry.attempt + 1
+        )
+    }
+}
+
+/// Border plus name and status lines, plus a line per listed server peer.
+fn row_height(iface: &InterfaceInfo) -> u16 {
+    let clients = iface.clients.len();
+    let listed = clients.min(MAX_LISTED_CLIENTS) + usize::from(clients > MAX_LISTED_CLIENTS);
+    4 + listed as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_validates_host_port_and_name() {
+        let taken = |name: &str| name == "Home";
+        let mut form = InterfaceForm::new(InterfaceKind::TcpClient);
+        form.name = "Hub".into();
+        form.host = "rns.example-net.org".into();
+        let (name, config) = form.to_config(taken).unwrap();
+        assert_eq!(name, "Hub");
+        assert!(matches!(
+            config,
+            InterfaceConfig::TCPClientInterface {
+                target_port: 4242,
+                ..
+            }
+        ));
+
+        for host in ["", "bad host", "-x.org", "a..b"] {
+            form.host = host.into();
+            assert!(form.to_config(taken).is_err(), "{:?}", host);
+        }
+        form.host = "10.0.0.1".into();
+        for port in ["0", "65536", "http"] {
+            form.port = port.into();
+            assert!(form.to_config(taken).is_err(), "{:?}", port);
+        }
+        form.port = "4965".into();
+        form.name = "Home".into();
+        assert!(form.to_config(taken).is_err());
+        form.original = Some("Home".into());
+        assert!(form.to_config(taken).is_ok());
+
+        let mut server = InterfaceForm::new(InterfaceKind::TcpServer);
+        server.name = "LAN".into();
+        assert!(server.to_config(taken).is_ok());
+        server.host = "::".into();
+        assert!(server.to_config(taken).is_ok());
+        server.host = "localhost".into();
+        assert!(server.to_config(taken).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::downloads::sanitize_filename;
 use crate::network::NodeInfo;
 
 const MICRON_EXTENSIONS: &[&str] = &["", "mu", "md", "micron"];
@@ -13,9 +14,11 @@ pub enum LinkAction {
         path: String,
         filename: String,
     },
-    Lxmf {
+    Message {
         hash: [u8; 16],
     },
+    /// An LXMF address, which nomad can't message.
+    Lxmf,
     Unknown {
         url: String,
     },
@@ -26,13 +29,16 @@ pub fn resolve_link(
     current_node: Option<&NodeInfo>,
     known_nodes: &[NodeInfo],
 ) -> LinkAction {
-    if let Some(hash) = parse_lxmf_link(link_url) {
-        return LinkAction::Lxmf { hash };
+    if let Some(hash) = parse_address_link(link_url, "nomad@") {
+        return LinkAction::Message { hash };
+    }
+    if parse_address_link(link_url, "lxmf@").is_some() {
+        return LinkAction::Lxmf;
     }
 
     if let Some((node, path)) = resolve_node_link(link_url, current_node, known_nodes) {
         if is_download_path(&path) {
-            let filename = extract_filename(&path);
+            let filename = sanitize_filename(&path);
             return LinkAction::Download {
                 node,
                 path,
@@ -47,8 +53,8 @@ pub fn resolve_link(
     }
 }
 
-fn parse_lxmf_link(url: &str) -> Option<[u8; 16]> {
-    let rest = url.strip_prefix("lxmf@")?;
+fn parse_address_link(url: &str, scheme: &str) -> Option<[u8; 16]> {
+    let rest = url.strip_prefix(scheme)?;
 
     if rest.len() != 32 {
         return None;
@@ -142,10 +148,6 @@ fn is_download_path(path: &str) -> bool {
     !MICRON_EXTENSIONS.contains(&ext.as_str())
 }
 
-fn extract_filename(path: &str) -> String {
-    path.rsplit('/').next().unwrap_or("download").to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,10 +161,11 @@ mod tests {
     }
 
     #[test]
-    fn test_lxmf_link() {
-        let url = "lxmf@0123456789abcdef0123456789abcdef";
-        let action = resolve_link(url, None, &[]);
-        assert!(matches!(action, LinkAction::Lxmf { .. }));
+    fn test_message_links() {
+        let action = resolve_link("nomad@0123456789abcdef0123456789abcdef", None, &[]);
+        assert!(matches!(action, LinkAction::Message { hash } if hash[0] == 0x01));
+        let action = resolve_link("lxmf@0123456789abcdef0123456789abcdef", None, &[]);
+        assert!(matches!(action, LinkAction::Lxmf));
     }
 
     #[test]
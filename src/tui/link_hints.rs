@@ -0,0 +1,145 @@
+use micronaut::Interaction;
+
+/// Home-row first so the common labels are the easiest to type.
+const HINT_CHARS: &[char] = &[
+    's', 'a', 'd', 'f', 'j', 'k', 'l', 'e', 'w', 'c', 'm', 'p', 'g', 'h',
+];
+
+/// Labels of equal length for `count` targets, so no label is a prefix of
+/// another.
+pub fn hint_labels(count: usize) -> Vec<String> {
+    let mut len = 1;
+    while HINT_CHARS.len().pow(len) < count {
+        len += 1;
+    }
+
+    (0..count)
+        .map(|mut n| {
+            let mut label = vec![' '; len as usize];
+            for slot in label.iter_mut().rev() {
+                *slot = HINT_CHARS[n % HINT_CHARS.len()];
+                n /= HINT_CHARS.len();
+            }
+            label.into_iter().collect()
+        })
+        .collect()
+}
+
+/// Identifies what a cell activates, so neighbouring cells of one link
+/// collapse into a single hint.
+pub fn interaction_key(interaction: &Interaction) -> String {
+    match interaction {
+        Interaction::Link(link) => format!("link:{}:{}", link.url, link.fields.join(",")),
+        Interaction::EditField(field) => format!("field:{}", field.name),
+        Interaction::RefreshPartials(ids) => format!("partials:{}", ids.join(",")),
+    }
+}
+
+pub struct HintTarget {
+    pub x: u16,
+    pub y: u16,
+    pub label: String,
+    pub interaction: Interaction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintOutcome {
+    Pending,
+    NoMatch,
+    Selected,
+}
+
+pub struct LinkHints {
+    pub targets: Vec<HintTarget>,
+    pub typed: String,
+}
+
+impl LinkHints {
+    pub fn new(found: Vec<(u16, u16, Interaction)>) -> Self {
+        let labels = hint_labels(found.len());
+        let targets = found
+            .into_iter()
+            .zip(labels)
+            .map(|((x, y, interaction), label)| HintTarget {
+                x,
+                y,
+                label,
+                interaction,
+            })
+            .collect();
+        Self {
+            targets,
+            typed: String::new(),
+        }
+    }
+
+    pub fn push(&mut self, c: char) -> HintOutcome {
+        self.typed.push(c.to_ascii_lowercase());
+        let mut candidates = self
+            .targets
+            .iter()
+            .filter(|t| t.label.starts_with(&self.typed));
+        match (candidates.next(), candidates.next()) {
+            (None, _) => HintOutcome::NoMatch,
+            (Some(t), None) if t.label == self.typed => HintOutcome::Selected,
+            _ => HintOutcome::Pending,
+        }
+    }
+
+    pub fn pop(&mut self) {
+        self.typed.pop();
+    }
+
+    pub fn take_selected(self) -> Option<Interaction> {
+        self.targets
+            .into_iter()
+            .find(|t| t.label == self.typed)
+            .map(|t| t.interaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use micronaut::Link;
+    use std::collections::HashMap;
+
+    fn link(url: &str) -> Interaction {
+        Interaction::Link(Link {
+            url: url.to_string(),
+            fields: Vec::new(),
+            form_data: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_labels_are_prefix_free() {
+        assert_eq!(hint_labels(3), ["s", "a", "d"]);
+
+        let labels = hint_labels(40);
+        assert_eq!(labels.len(), 40);
+        assert!(labels.iter().all(|l| l.len() == 2));
+        assert_eq!(labels[0], "ss");
+        assert_eq!(labels[14], "as");
+    }
+
+    #[test]
+    fn test_typing_selects_target() {
+        let found = (0..20)
+            .map(|i| (0, i, link(&format!(":/page/{}.mu", i))))
+            .collect();
+        let mut hints = LinkHints::new(found);
+
+        assert_eq!(hints.push('a'), HintOutcome::Pending);
+        assert_eq!(hints.push('D'), HintOutcome::Selected);
+        match hints.take_selected() {
+            Some(Interaction::Link(link)) => assert_eq!(link.url, ":/page/16.mu"),
+            _ => panic!("expected a link"),
+        }
+
+        let mut hints = LinkHints::new(vec![(0, 0, link(":/a.mu"))]);
+        assert_eq!(hints.push('x'), HintOutcome::NoMatch);
+        hints.pop();
+        assert_eq!(hints.push('s'), HintOutcome::Selected);
+    }
+}
@@ -1,11 +1,19 @@
 mod app;
+mod browser_tabs;
 mod browser_view;
+mod conversations;
 mod directory;
 mod discovery;
+mod downloads;
+mod find;
+mod fuzzy;
+mod history;
 mod interfaces;
 mod link_handler;
+mod link_hints;
 mod modal;
 mod mynode;
+pub(crate) mod page_source;
 mod saved;
 mod status_bar;
 mod tabs;
@@ -13,6 +21,10 @@ mod tabs;
 pub use app::{NetworkEvent, TuiApp, TuiCommand};
 pub use interfaces::{InterfaceInfo, InterfaceKind};
 
+use std::collections::VecDeque;
+
+const SPARK_CHARS: [char; 8] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇'];
+
 pub(crate) fn format_bytes(bytes: u64) -> String {
     if bytes >= 1_000_000_000 {
         format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
@@ -36,3 +48,26 @@ pub(crate) fn format_uptime(secs: u64) -> String {
         format!("{}s", secs)
     }
 }
+
+/// The last `width` values as block characters scaled to the largest,
+/// right-aligned in `width` columns.
+pub(crate) fn sparkline(history: &VecDeque<u64>, width: usize) -> String {
+    if history.is_empty() {
+        return " ".repeat(width);
+    }
+
+    let max_val = history.iter().max().copied().unwrap_or(1).max(1);
+    let start = history.len().saturating_sub(width);
+
+    let mut result = String::with_capacity(width);
+    for &val in history.iter().skip(start) {
+        let idx = ((val as f64 / max_val as f64) * 7.0) as usize;
+        result.push(SPARK_CHARS[idx.min(7)]);
+    }
+
+    while result.chars().count() < width {
+        result.insert(0, ' ');
+    }
+
+    result
+}
@@ -10,7 +10,6 @@ use std::collections::VecDeque;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const HISTORY_SIZE: usize = 60;
-const SPARK_CHARS: [char; 8] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇'];
 
 pub struct MyNodeView {
     node_hash: [u8; 16],
@@ -106,39 +105,6 @@ impl MyNodeView {
         }
     }
 
-    fn render_sparkline(&self, width: usize) -> String {
-        if self.bytes_per_sec_history.is_empty() {
-            return " ".repeat(width);
-        }
-
-        let max_val = self
-            .bytes_per_sec_history
-            .iter()
-            .max()
-            .copied()
-            .unwrap_or(1)
-            .max(1);
-        let history: Vec<u64> = self.bytes_per_sec_history.iter().copied().collect();
-        let start = history.len().saturating_sub(width);
-        let visible = &history[start..];
-
-        let mut result = String::with_capacity(width);
-        for &val in visible {
-            let idx = if max_val > 0 {
-                ((val as f64 / max_val as f64) * 7.0) as usize
-            } else {
-                0
-            };
-            result.push(SPARK_CHARS[idx.min(7)]);
-        }
-
-        while result.chars().count() < width {
-            result.insert(0, ' ');
-        }
-
-        result
-    }
-
     fn render_identity_card(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
             .title(Line::from(vec![Span::styled(
@@ -284,7 +250,7 @@ impl MyNodeView {
             let relay_bytes = super::format_bytes(stats.bytes_relayed);
 
             let chart_width = inner.width.saturating_sub(6) as usize;
-            let sparkline = self.render_sparkline(chart_width);
+            let sparkline = super::sparkline(&self.bytes_per_sec_history, chart_width);
 
             let mut lines = vec![
                 Line::from(""),
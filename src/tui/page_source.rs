@@ -0,0 +1,210 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// A page response decoded from its msgpack `(timestamp, path, content)`
+/// envelope. Responses that don't decode keep the raw bytes as content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageEnvelope {
+    pub timestamp: Option<f64>,
+    pub path: Option<String>,
+    pub content: String,
+    pub size: usize,
+}
+
+impl PageEnvelope {
+    pub fn decode(data: &[u8]) -> Self {
+        if let Ok((timestamp, path, Some(content))) =
+            rmp_serde::from_slice::<(f64, Vec<u8>, Option<Vec<u8>>)>(data)
+        {
+            let path = match String::from_utf8(path) {
+                Ok(path) => path,
+                Err(e) => hex::encode(e.into_bytes()),
+            };
+            return Self {
+                timestamp: Some(timestamp),
+                path: Some(path),
+                content: String::from_utf8_lossy(&content).into_owned(),
+                size: data.len(),
+            };
+        }
+        Self {
+            timestamp: None,
+            path: None,
+            content: String::from_utf8_lossy(data).into_owned(),
+            size: data.len(),
+        }
+    }
+}
+
+fn tag_style() -> Style {
+    Style::default().fg(Color::Yellow)
+}
+
+/// Highlights micron markup line by line: headings, comments, directives,
+/// dividers, formatting tags, links and fields.
+pub fn highlight_micron(source: &str) -> Vec<Line<'static>> {
+    let mut literal = false;
+    source
+        .lines()
+        .map(|line| highlight_line(line, &mut literal))
+        .collect()
+}
+
+fn highlight_line(line: &str, literal: &mut bool) -> Line<'static> {
+    if line == "`=" {
+        *literal = !*literal;
+        return Line::from(Span::styled(line.to_string(), tag_style()));
+    }
+    if *literal {
+        return Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    if line.starts_with("#!") {
+        return Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    if line.starts_with('#') {
+        return Line::from(Span::styled(
+            line.to_string(),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        ));
+    }
+    if line.starts_with('-') && line.chars().count() <= 2 {
+        return Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let marker_len = line.len() - line.trim_start_matches(['>', '<']).len();
+    if marker_len > 0 {
+        let (marker, rest) = line.split_at(marker_len);
+        let base = if marker.starts_with('>') {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let mut spans = vec![Span::styled(
+            marker.to_string(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        spans.extend(highlight_inline(rest, base));
+        return Line::from(spans);
+    }
+
+    Line::from(highlight_inline(line, Style::default().fg(Color::Gray)))
+}
+
+fn highlight_inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '`' {
+            plain.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut plain), base));
+        }
+
+        let closing = |close: char| {
+            chars[i..]
+                .iter()
+                .position(|&c| c == close)
+                .map_or(chars.len() - i, |p| p + 1)
+        };
+        let (len, style) = match chars.get(i + 1) {
+            Some('[') => (
+                closing(']'),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+            Some('<') => (closing('>'), Style::default().fg(Color::Green)),
+            Some('F') | Some('B') => (5, tag_style()),
+            Some(_) => (2, tag_style()),
+            None => (1, tag_style()),
+        };
+        let end = (i + len).min(chars.len());
+        spans.push(Span::styled(
+            chars[i..end].iter().collect::<String>(),
+            style,
+        ));
+        i = end;
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_envelope() {
+        let data = rmp_serde::to_vec(&(
+            1_700_000_000.5f64,
+            b"/page/index.mu".to_vec(),
+            Some(b">Hello".to_vec()),
+        ))
+        .unwrap();
+        let envelope = PageEnvelope::decode(&data);
+        assert_eq!(envelope.timestamp, Some(1_700_000_000.5));
+        assert_eq!(envelope.path.as_deref(), Some("/page/index.mu"));
+        assert_eq!(envelope.content, ">Hello");
+        assert_eq!(envelope.size, data.len());
+
+        let raw = PageEnvelope::decode(b"plain text");
+        assert_eq!(raw.timestamp, None);
+        assert_eq!(raw.content, "plain text");
+    }
+
+    #[test]
+    fn test_highlight_micron() {
+        let lines = highlight_micron(
+            "#!c=0\n# note\n>>Title\nSee `[Home`:/page/index.mu] and `!bold`!\n`=\n`[not a link]\n`=",
+        );
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Magenta));
+        assert_eq!(lines[1].spans[0].style.fg, Some(Color::DarkGray));
+        assert_eq!(lines[2].spans[0].content, ">>");
+        assert_eq!(lines[2].spans[1].content, "Title");
+
+        let body: Vec<&str> = lines[3].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            body,
+            [
+                "See ",
+                "`[Home`:/page/index.mu]",
+                " and ",
+                "`!",
+                "bold",
+                "`!"
+            ]
+        );
+        assert_eq!(lines[3].spans[1].style.fg, Some(Color::Cyan));
+
+        assert_eq!(lines[5].spans.len(), 1);
+        assert_eq!(lines[5].spans[0].style.fg, Some(Color::Gray));
+    }
+}
@@ -1,3 +1,6 @@
+use super::discovery::filter_title;
+use super::fuzzy;
+use crate::bookmarks::Bookmark;
 use crate::network::NodeInfo;
 use ratatui::{
     buffer::Buffer,
@@ -16,8 +19,21 @@ pub enum SavedModalAction {
     ToggleIdentify,
 }
 
+/// A line in the saved list: a node, or one of its bookmarked pages listed
+/// underneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SavedRow {
+    Node(usize),
+    Bookmark(usize),
+}
+
 pub struct SavedView {
     nodes: Vec<NodeInfo>,
+    bookmarks: Vec<Bookmark>,
+    rows: Vec<SavedRow>,
+    /// Text typed after `/`, matched against node names, hashes and
+    /// bookmark titles.
+    filter: String,
     list_state: ListState,
     last_height: usize,
     last_list_area: Rect,
@@ -37,6 +53,9 @@ impl SavedView {
     pub fn new() -> Self {
         Self {
             nodes: Vec::new(),
+            bookmarks: Vec::new(),
+            rows: Vec::new(),
+            filter: String::new(),
             list_state: ListState::default(),
             last_height: 10,
             last_list_area: Rect::default(),
@@ -58,15 +77,142 @@ impl SavedView {
                 .binary_search_by(|n| n.name.to_lowercase().cmp(&node.name.to_lowercase()))
                 .unwrap_or_else(|p| p);
             self.nodes.insert(pos, node);
+            self.rebuild_rows();
         }
     }
 
     pub fn select_by_hash(&mut self, hash: [u8; 16]) {
         if let Some(pos) = self.nodes.iter().position(|n| n.hash == hash) {
+            self.select_row(SavedRow::Node(pos));
+        }
+    }
+
+    pub fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+        self.bookmarks = bookmarks;
+        self.rebuild_rows();
+    }
+
+    /// Adds or replaces a bookmark and selects it.
+    pub fn save_bookmark(&mut self, bookmark: Bookmark) {
+        let idx = match self.bookmarks.iter().position(|b| b.url == bookmark.url) {
+            Some(idx) => {
+                self.bookmarks[idx] = bookmark;
+                idx
+            }
+            None => {
+                self.bookmarks.push(bookmark);
+                self.bookmarks.len() - 1
+            }
+        };
+        self.rebuild_rows();
+        self.select_row(SavedRow::Bookmark(idx));
+    }
+
+    pub fn selected_bookmark(&self) -> Option<&Bookmark> {
+        match self.selected_row()? {
+            SavedRow::Bookmark(idx) => self.bookmarks.get(idx),
+            SavedRow::Node(_) => None,
+        }
+    }
+
+    pub fn remove_selected_bookmark(&mut self) -> Option<Bookmark> {
+        let SavedRow::Bookmark(idx) = self.selected_row()? else {
+            return None;
+        };
+        let removed = self.bookmarks.remove(idx);
+        self.rebuild_rows();
+        self.clamp_selection();
+        Some(removed)
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Narrows the list as the user types, keeping the selection if it
+    /// still matches.
+    pub fn set_filter(&mut self, filter: &str) {
+        if self.filter == filter {
+            return;
+        }
+        let selected = self.selected_row();
+        self.filter = filter.to_string();
+        self.rebuild_rows();
+        self.list_state.select(Some(0));
+        if let Some(row) = selected {
+            self.select_row(row);
+        }
+    }
+
+    fn selected_row(&self) -> Option<SavedRow> {
+        self.rows.get(self.selected()).copied()
+    }
+
+    fn select_row(&mut self, row: SavedRow) {
+        if let Some(pos) = self.rows.iter().position(|r| *r == row) {
             self.list_state.select(Some(pos));
         }
     }
 
+    fn clamp_selection(&mut self) {
+        if self.selected() >= self.rows.len() && !self.rows.is_empty() {
+            self.list_state.select(Some(self.rows.len() - 1));
+        }
+    }
+
+    /// Lays out each node followed by its bookmarks. Bookmarks whose node
+    /// isn't saved go at the end so they stay reachable.
+    ///
+    /// While filtering, a matching node keeps all its bookmarks, a node with
+    /// only matching bookmarks keeps just those, and the best matching nodes
+    /// come first.
+    fn rebuild_rows(&mut self) {
+        let mut bookmark_order: Vec<usize> = (0..self.bookmarks.len()).collect();
+        bookmark_order.sort_by_key(|&i| self.bookmarks[i].title.to_lowercase());
+        let bookmark_matches: Vec<bool> = self
+            .bookmarks
+            .iter()
+            .map(|b| fuzzy::score(&self.filter, &b.title, "").is_some())
+            .collect();
+
+        let mut node_order: Vec<(usize, Option<u32>)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (i, fuzzy::score(&self.filter, &n.name, &n.hash_hex())))
+            .collect();
+        node_order.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+        let mut rows = Vec::with_capacity(self.nodes.len() + self.bookmarks.len());
+        for (node_idx, score) in node_order {
+            let hash = self.nodes[node_idx].hash;
+            let bookmarks: Vec<SavedRow> = bookmark_order
+                .iter()
+                .filter(|&&i| self.bookmarks[i].node_hash() == Some(hash))
+                .filter(|&&i| score.is_some() || bookmark_matches[i])
+                .map(|&i| SavedRow::Bookmark(i))
+                .collect();
+            if score.is_none() && bookmarks.is_empty() {
+                continue;
+            }
+            rows.push(SavedRow::Node(node_idx));
+            rows.extend(bookmarks);
+        }
+        rows.extend(
+            bookmark_order
+                .iter()
+                .filter(|&&i| {
+                    bookmark_matches[i]
+                        && !self
+                            .nodes
+                            .iter()
+                            .any(|n| self.bookmarks[i].node_hash() == Some(n.hash))
+                })
+                .map(|&i| SavedRow::Bookmark(i)),
+        );
+        self.rows = rows;
+    }
+
     pub fn nodes(&self) -> &[NodeInfo] {
         &self.nodes
     }
@@ -75,27 +221,34 @@ impl SavedView {
         self.nodes.len()
     }
 
+    /// The selected node, or the node a selected bookmark belongs to.
     pub fn selected_node(&self) -> Option<&NodeInfo> {
-        self.nodes.get(self.selected())
+        match self.selected_row()? {
+            SavedRow::Node(idx) => self.nodes.get(idx),
+            SavedRow::Bookmark(idx) => {
+                let hash = self.bookmarks[idx].node_hash()?;
+                self.nodes.iter().find(|n| n.hash == hash)
+            }
+        }
     }
 
     pub fn select_next(&mut self) {
-        if self.nodes.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
         let i = match self.list_state.selected() {
-            Some(i) => (i + 1) % self.nodes.len(),
+            Some(i) => (i + 1) % self.rows.len(),
             None => 0,
         };
         self.list_state.select(Some(i));
     }
 
     pub fn select_prev(&mut self) {
-        if self.nodes.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
         let i = match self.list_state.selected() {
-            Some(i) => i.checked_sub(1).unwrap_or(self.nodes.len() - 1),
+            Some(i) => i.checked_sub(1).unwrap_or(self.rows.len() - 1),
             None => 0,
         };
         self.list_state.select(Some(i));
@@ -125,7 +278,7 @@ impl SavedView {
         let offset = self.list_state.offset();
         let idx = offset + inner_y as usize;
 
-        if idx < self.nodes.len() {
+        if idx < self.rows.len() {
             self.list_state.select(Some(idx));
             Some(idx)
         } else {
@@ -133,26 +286,24 @@ impl SavedView {
         }
     }
 
+    /// Removes the selected node along with its bookmarks.
     pub fn remove_selected(&mut self) -> Option<NodeInfo> {
-        if self.nodes.is_empty() {
+        let SavedRow::Node(idx) = self.selected_row()? else {
             return None;
-        }
-        let selected = self.selected();
-        let removed = self.nodes.remove(selected);
-        if selected >= self.nodes.len() && !self.nodes.is_empty() {
-            self.list_state.select(Some(self.nodes.len() - 1));
-        }
+        };
+        let removed = self.nodes.remove(idx);
+        self.bookmarks
+            .retain(|b| b.node_hash() != Some(removed.hash));
+        self.rebuild_rows();
+        self.clamp_selection();
         Some(removed)
     }
 
     pub fn toggle_identify_selected(&mut self) -> Option<&NodeInfo> {
-        let selected = self.selected();
-        if let Some(node) = self.nodes.get_mut(selected) {
-            node.identify = !node.identify;
-            Some(node)
-        } else {
-            None
-        }
+        let hash = self.selected_node()?.hash;
+        let node = self.nodes.iter_mut().find(|n| n.hash == hash)?;
+        node.identify = !node.identify;
+        Some(node)
     }
 
     pub fn set_identify(&mut self, hash: [u8; 16], enabled: bool) {
@@ -172,7 +323,17 @@ impl SavedView {
     fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
         self.last_list_area = area;
 
-        let block = Block::default()
+        let shown = self
+            .rows
+            .iter()
+            .filter(|row| matches!(row, SavedRow::Node(_)))
+            .count();
+        let count = if self.filter.is_empty() {
+            format!("({}) ", self.nodes.len())
+        } else {
+            format!("({} of {}) ", shown, self.nodes.len())
+        };
+        let mut block = Block::default()
             .title(Line::from(vec![
                 Span::styled(
                     " Saved Nodes ",
@@ -180,19 +341,31 @@ impl SavedView {
                         .fg(Color::White)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(
-                    format!("({}) ", self.nodes.len()),
-                    Style::default().fg(Color::DarkGray),
-                ),
+                Span::styled(count, Style::default().fg(Color::DarkGray)),
             ]))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray));
+        if !self.filter.is_empty() {
+            block = block.title_bottom(filter_title(&self.filter));
+        }
 
         let inner = block.inner(area);
         self.last_height = inner.height as usize;
         block.render(area, buf);
 
-        if self.nodes.is_empty() {
+        if self.rows.is_empty() && !self.filter.is_empty() {
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Nothing matches",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(inner, buf);
+            return;
+        }
+        if self.rows.is_empty() {
             let empty_lines = vec![
                 Line::from(""),
                 Line::from(Span::styled(
@@ -212,18 +385,33 @@ impl SavedView {
         }
 
         let items: Vec<ListItem> = self
-            .nodes
+            .rows
             .iter()
-            .map(|node| {
-                let hash_short = format!("{}..{}", &node.hash_hex()[..6], &node.hash_hex()[26..]);
-                ListItem::new(Line::from(vec![
-                    Span::styled(" \u{2022} ", Style::default().fg(Color::Green)),
-                    Span::styled(&node.name, Style::default().fg(Color::Gray)),
-                    Span::styled(
-                        format!("  {}", hash_short),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                ]))
+            .map(|row| match *row {
+                SavedRow::Node(idx) => {
+                    let node = &self.nodes[idx];
+                    let hash_short =
+                        format!("{}..{}", &node.hash_hex()[..6], &node.hash_hex()[26..]);
+                    ListItem::new(Line::from(vec![
+                        Span::styled(" \u{2022} ", Style::default().fg(Color::Green)),
+                        Span::styled(&node.name, Style::default().fg(Color::Gray)),
+                        Span::styled(
+                            format!("  {}", hash_short),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]))
+                }
+                SavedRow::Bookmark(idx) => {
+                    let bookmark = &self.bookmarks[idx];
+                    ListItem::new(Line::from(vec![
+                        Span::styled("   \u{21b3} ", Style::default().fg(Color::Yellow)),
+                        Span::styled(&bookmark.title, Style::default().fg(Color::Gray)),
+                        Span::styled(
+                            format!("  {}", bookmark.path()),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]))
+                }
             })
             .collect();
 
@@ -245,9 +433,16 @@ impl SavedView {
         self.copy_button_area = None;
         self.delete_button_area = None;
 
+        let bookmark = self.selected_bookmark().cloned();
+        let title = if bookmark.is_some() {
+            " Page Info "
+        } else {
+            " Node Info "
+        };
+
         let block = Block::default()
             .title(Line::from(vec![Span::styled(
-                " Node Info ",
+                title,
                 Style::default().fg(Color::White),
             )]))
             .borders(Borders::ALL)
@@ -256,6 +451,12 @@ impl SavedView {
         let inner = block.inner(area);
         block.render(area, buf);
 
+        if let Some(bookmark) = bookmark {
+            self.render_bookmark_detail(&bookmark, inner, buf);
+            self.render_actions(inner, buf, " Open ");
+            return;
+        }
+
         let Some(node) = self.selected_node() else {
             let empty = Paragraph::new(Line::from(Span::styled(
                 "Select a node to view details",
@@ -314,7 +515,70 @@ impl SavedView {
         buf.set_string(inner.x, identify_y, identify_text, identify_style);
         self.identify_button_area = Some(Rect::new(inner.x, identify_y, identify_width, 1));
 
-        // Action buttons at bottom: Delete | Copy | Connect
+        self.render_actions(inner, buf, " Connect ");
+    }
+
+    fn render_bookmark_detail(&self, bookmark: &Bookmark, inner: Rect, buf: &mut Buffer) {
+        let node_name = self
+            .selected_node()
+            .map(|n| n.name.clone())
+            .unwrap_or_else(|| "Unsaved node".to_string());
+
+        let mut content = vec![
+            Line::from(Span::styled(
+                bookmark.title.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Node: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(node_name, Style::default().fg(Color::Gray)),
+            ]),
+            Line::from(vec![
+                Span::styled("Path: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    bookmark.path().to_string(),
+                    Style::default().fg(Color::Cyan),
+                ),
+            ]),
+        ];
+
+        if !bookmark.form_data.is_empty() {
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "Form data:",
+                Style::default().fg(Color::DarkGray),
+            )));
+            let mut fields: Vec<_> = bookmark.form_data.iter().collect();
+            fields.sort();
+            for (key, value) in fields {
+                content.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {} = ", key),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(value.clone(), Style::default().fg(Color::Gray)),
+                ]));
+            }
+        }
+
+        Paragraph::new(content)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .render(
+                Rect::new(
+                    inner.x,
+                    inner.y,
+                    inner.width,
+                    inner.height.saturating_sub(2),
+                ),
+                buf,
+            );
+    }
+
+    /// Action buttons at bottom: Delete | Copy | Connect
+    fn render_actions(&mut self, inner: Rect, buf: &mut Buffer, connect_text: &str) {
         let button_y = inner.y + inner.height.saturating_sub(1);
         let mut x = inner.x;
 
@@ -336,7 +600,6 @@ impl SavedView {
         self.copy_button_area = Some(Rect::new(x, button_y, copy_text.len() as u16, 1));
         x += copy_text.len() as u16 + 1;
 
-        let connect_text = " Connect ";
         let connect_style = Style::default()
             .fg(Color::Black)
             .bg(Color::Magenta)
@@ -346,7 +609,7 @@ impl SavedView {
     }
 
     pub fn click_detail(&mut self, x: u16, y: u16) -> SavedModalAction {
-        if self.nodes.is_empty() {
+        if self.rows.is_empty() {
             return SavedModalAction::None;
         }
 
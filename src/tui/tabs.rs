@@ -11,15 +11,21 @@ pub enum Tab {
     Discovery,
     Saved,
     Browser,
+    Conversations,
+    History,
+    Downloads,
     MyNode,
     Interfaces,
 }
 
 impl Tab {
-    pub const ALL: [Tab; 5] = [
+    pub const ALL: [Tab; 8] = [
         Tab::Discovery,
         Tab::Saved,
         Tab::Browser,
+        Tab::Conversations,
+        Tab::History,
+        Tab::Downloads,
         Tab::MyNode,
         Tab::Interfaces,
     ];
@@ -29,6 +35,9 @@ impl Tab {
             Tab::Discovery => "Discovery",
             Tab::Saved => "Saved",
             Tab::Browser => "Browser",
+            Tab::Conversations => "Conversations",
+            Tab::History => "History",
+            Tab::Downloads => "Downloads",
             Tab::MyNode => "My Node",
             Tab::Interfaces => "Interfaces",
         }
@@ -38,7 +47,10 @@ impl Tab {
         match self {
             Tab::Discovery => Tab::Saved,
             Tab::Saved => Tab::Browser,
-            Tab::Browser => Tab::MyNode,
+            Tab::Browser => Tab::Conversations,
+            Tab::Conversations => Tab::History,
+            Tab::History => Tab::Downloads,
+            Tab::Downloads => Tab::MyNode,
             Tab::MyNode => Tab::Interfaces,
             Tab::Interfaces => Tab::Discovery,
         }
@@ -49,7 +61,10 @@ impl Tab {
             Tab::Discovery => Tab::Interfaces,
             Tab::Saved => Tab::Discovery,
             Tab::Browser => Tab::Saved,
-            Tab::MyNode => Tab::Browser,
+            Tab::Conversations => Tab::Browser,
+            Tab::History => Tab::Conversations,
+            Tab::Downloads => Tab::History,
+            Tab::MyNode => Tab::Downloads,
             Tab::Interfaces => Tab::MyNode,
         }
     }